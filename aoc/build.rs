@@ -0,0 +1,47 @@
+//! With the `embed` feature, compile every `day-NN/data/*.txt` into the crate,
+//! see `input::embedded`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The inputs of every day of the calendar. Days without data are watched too,
+/// so that their inputs are embedded once added; cargo reruns this script on
+/// every build while some are missing.
+fn data_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for day in 1..=25 {
+        let dir = root.join(format!("day-{:02}", day)).join("data");
+        println!("cargo:rerun-if-changed={}", dir.display());
+        let Ok(data) = fs::read_dir(&dir) else {
+            continue;
+        };
+        files.extend(
+            data.flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|e| e == "txt")),
+        );
+    }
+    files.sort();
+    files
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("embedded.rs");
+    let mut table = String::from("&[\n");
+    if env::var_os("CARGO_FEATURE_EMBED").is_some() {
+        let manifest = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+        let root = manifest.parent().unwrap();
+        for path in data_files(root) {
+            let key = path.strip_prefix(root).unwrap();
+            table.push_str(&format!(
+                "    ({:?}, include_bytes!({:?})),\n",
+                key.to_string_lossy(),
+                path.to_string_lossy()
+            ));
+        }
+    }
+    table.push_str("]\n");
+    fs::write(out, table).unwrap();
+}
@@ -0,0 +1,63 @@
+//! Inputs rewritten so that they can be shared, for the runner's
+//! `anonymize` command.
+
+use anyhow::Result;
+use num_bigint::BigInt;
+
+use crate::diff::Rng;
+use crate::{Answer, Solution};
+
+/// An input equivalent to another one, and how their answers relate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anonymized {
+    pub input: String,
+    /// Added to the integer answers of the original input.
+    pub shift: i64,
+}
+
+impl Anonymized {
+    /// An input with the same answers as the original.
+    pub fn same(input: String) -> Self {
+        Anonymized { input, shift: 0 }
+    }
+
+    /// The answer to the anonymized input, from that to the original.
+    pub fn expected(&self, answer: &Answer) -> Answer {
+        match answer {
+            Answer::Int(n) => Answer::from(i128::from(*n) + i128::from(self.shift)),
+            Answer::Big(n) => Answer::from(n + BigInt::from(self.shift)),
+            answer => answer.clone(),
+        }
+    }
+}
+
+/// A [`Solution`] that can hide the specifics of its inputs.
+pub trait Anonymize: Solution {
+    /// Rewrite `input` with values drawn from `rng`, such that it still is a
+    /// valid input whose answers follow from the original ones.
+    fn anonymize(input: &str, rng: &mut Rng) -> Result<Anonymized>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected() {
+        let anonymized = Anonymized {
+            input: String::new(),
+            shift: 10,
+        };
+        assert_eq!(anonymized.expected(&Answer::Int(5)), Answer::Int(15));
+        assert_eq!(
+            anonymized.expected(&Answer::Int(i64::MAX)),
+            Answer::from(i128::from(i64::MAX) + 10)
+        );
+        assert_eq!(
+            anonymized.expected(&Answer::text("ABC")),
+            Answer::text("ABC")
+        );
+        let same = Anonymized::same(String::new());
+        assert_eq!(same.expected(&Answer::Int(5)), Answer::Int(5));
+    }
+}
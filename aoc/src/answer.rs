@@ -0,0 +1,163 @@
+use std::fmt;
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+
+/// The answer to a part of a puzzle.
+///
+/// Values are normalized on construction, so that equal answers compare
+/// equal: integers that fit an `i64` are always [`Answer::Int`], text is
+/// trimmed, and grids lose their trailing whitespace and blank lines.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    Big(BigInt),
+    Text(String),
+    /// Several lines, e.g. letters drawn in ASCII art.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    pub fn text(text: impl AsRef<str>) -> Self {
+        Answer::Text(text.as_ref().trim().to_string())
+    }
+
+    pub fn grid<I>(rows: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut rows: Vec<String> = rows
+            .into_iter()
+            .map(|row| row.as_ref().trim_end().to_string())
+            .collect();
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        while rows.first().is_some_and(|row| row.is_empty()) {
+            rows.remove(0);
+        }
+        Answer::Grid(rows)
+    }
+
+    /// The name of the variant, as used in reports.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Int(_) => "int",
+            Answer::Big(_) => "big",
+            Answer::Text(_) => "text",
+            Answer::Grid(_) => "grid",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = String;
+
+    /// Parse a displayed answer back, taking the most specific variant.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_end();
+        if s.trim().is_empty() {
+            return Err("empty answer".to_string());
+        }
+        if s.trim().contains('\n') {
+            return Ok(Answer::grid(s.lines()));
+        }
+        let s = s.trim();
+        if let Ok(n) = s.parse::<i64>() {
+            return Ok(Answer::Int(n));
+        }
+        if let Ok(n) = s.parse::<BigInt>() {
+            return Ok(Answer::Big(n));
+        }
+        Ok(Answer::text(s))
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        match i64::try_from(&n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Big(n),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Int(n),
+                        Err(_) => Answer::Big(BigInt::from(n)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::text(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(Answer::from(42u64), Answer::Int(42));
+        assert_eq!(Answer::from(BigInt::from(-7)), Answer::Int(-7));
+        assert_eq!(Answer::from(u64::MAX), Answer::Big(BigInt::from(u64::MAX)));
+        assert_eq!(Answer::from(" abc\n"), Answer::Text("abc".to_string()));
+        assert_eq!(
+            Answer::grid(["", "#.# ", ".#.", ""]),
+            Answer::Grid(vec!["#.#".to_string(), ".#.".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_display_roundtrip() {
+        let answers = [
+            Answer::Int(-3),
+            Answer::from(u128::MAX),
+            Answer::text("JKL"),
+            Answer::grid(["#..#", "####"]),
+        ];
+        for answer in answers {
+            assert_eq!(answer.to_string().parse::<Answer>().unwrap(), answer);
+        }
+        assert_eq!(" 042\n".parse::<Answer>().unwrap(), Answer::Int(42));
+        assert_eq!(
+            "18446744073709551616"
+                .parse::<Answer>()
+                .unwrap()
+                .to_string(),
+            "18446744073709551616"
+        );
+        assert!(" \n".parse::<Answer>().is_err());
+    }
+}
@@ -0,0 +1,224 @@
+//! Puzzle inputs encrypted at rest, which [`input::read`](crate::input::read)
+//! decrypts transparently.
+//!
+//! An encrypted file is [`MAGIC`], a random nonce and the input sealed with
+//! ChaCha20-Poly1305. The key is 32 bytes written in hex, taken from
+//! [`KEY_VAR`], else from the file named by [`KEY_FILE_VAR`], else from the
+//! first [`KEY_FILE`] found from the current directory up.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+
+/// The start of every encrypted file.
+pub const MAGIC: &[u8] = b"aoc-encrypted-v1\n";
+/// Holds the key in hex.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+/// Names a file holding the key in hex.
+pub const KEY_FILE_VAR: &str = "AOC_INPUT_KEY_FILE";
+/// The key file looked for when neither variable is set.
+pub const KEY_FILE: &str = ".aoc-input-key";
+
+const NONCE_LEN: usize = 12;
+
+/// Whether `data` is an encrypted input.
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    pub fn generate() -> Self {
+        Key(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// Load the key from the environment or a key file.
+    pub fn load() -> Result<Self> {
+        if let Ok(hex) = env::var(KEY_VAR) {
+            return hex.parse().with_context(|| format!("invalid {}", KEY_VAR));
+        }
+        let path = match env::var_os(KEY_FILE_VAR) {
+            Some(path) => PathBuf::from(path),
+            None => find_key_file()?,
+        };
+        fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?
+            .parse()
+            .with_context(|| format!("invalid key in {}", path.display()))
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0.into())
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let sealed = self
+            .cipher()
+            .encrypt(&nonce, plaintext)
+            .expect("inputs fit the cipher");
+        [MAGIC, nonce.as_slice(), &sealed].concat()
+    }
+
+    /// The plaintext of `data`, which must have been encrypted with this key.
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let Some(rest) = data.strip_prefix(MAGIC) else {
+            bail!("not encrypted");
+        };
+        ensure!(rest.len() >= NONCE_LEN, "truncated");
+        let (nonce, sealed) = rest.split_at(NONCE_LEN);
+        self.cipher()
+            .decrypt(Nonce::from_slice(nonce), sealed)
+            .map_err(|_| anyhow!("wrong key or corrupted data"))
+    }
+}
+
+/// The first key file from the current directory up.
+fn find_key_file() -> Result<PathBuf> {
+    let cwd = env::current_dir()?;
+    cwd.ancestors()
+        .map(|dir| dir.join(KEY_FILE))
+        .find(|path| path.is_file())
+        .with_context(|| {
+            format!(
+                "no key: set {} or {}, or write {} above {}",
+                KEY_VAR,
+                KEY_FILE_VAR,
+                KEY_FILE,
+                cwd.display()
+            )
+        })
+}
+
+impl std::str::FromStr for Key {
+    type Err = anyhow::Error;
+
+    fn from_str(hex: &str) -> Result<Self> {
+        let hex = hex.trim();
+        ensure!(
+            hex.len() == 64,
+            "expected 64 hex digits, found {}",
+            hex.len()
+        );
+        let mut key = [0; 32];
+        for (byte, digits) in key.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            let digits = std::str::from_utf8(digits)?;
+            *byte = u8::from_str_radix(digits, 16)
+                .with_context(|| format!("invalid hex {:?}", digits))?;
+        }
+        Ok(Key(key))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+/// Keys are secrets, their bytes are left out.
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+/// Write a new key to `path`, only readable by its owner on unix. An existing
+/// file is only replaced with `force`, as inputs encrypted with it would be
+/// lost.
+pub fn write_key(path: &Path, force: bool) -> Result<Key> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true);
+    if force {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = match options.open(path) {
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            bail!(
+                "{} already exists, use --force to replace it",
+                path.display()
+            )
+        }
+        opened => opened.with_context(|| format!("failed to write {}", path.display()))?,
+    };
+    // The mode is only applied to new files.
+    #[cfg(unix)]
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    let key = Key::generate();
+    writeln!(file, "{}", key).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let key = Key::generate();
+        let sealed = key.encrypt(b"seeds: 79 14 55 13");
+        assert!(is_encrypted(&sealed));
+        assert_ne!(key.encrypt(b"seeds: 79 14 55 13"), sealed);
+        assert_eq!(key.decrypt(&sealed).unwrap(), b"seeds: 79 14 55 13");
+
+        assert!(Key::generate().decrypt(&sealed).is_err());
+        let mut corrupted = sealed.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert!(key.decrypt(&corrupted).is_err());
+        assert!(key.decrypt(&sealed[..MAGIC.len() + 4]).is_err());
+        assert!(key.decrypt(b"seeds").is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let key = Key::generate();
+        assert_eq!(key.to_string().parse::<Key>().unwrap(), key);
+        assert_eq!(format!("{:?}", key), "Key(..)");
+        assert!("00ff".parse::<Key>().is_err());
+        assert!("zz".repeat(32).parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn test_write_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(KEY_FILE);
+        let key = write_key(&path, false).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap().parse::<Key>().unwrap(),
+            key
+        );
+        assert!(write_key(&path, false).is_err());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap().parse::<Key>().unwrap(),
+            key
+        );
+
+        #[cfg(unix)]
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let replaced = write_key(&path, true).unwrap();
+        assert_ne!(replaced, key);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap().parse::<Key>().unwrap(),
+            replaced
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+    }
+}
@@ -0,0 +1,462 @@
+//! Differential testing: run every implementation of a part on generated
+//! inputs, and report the smallest input on which they disagree.
+
+use std::cell::Cell;
+use std::fmt;
+use std::iter;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use anyhow::{anyhow, Result};
+
+use crate::{Answer, Part, Solution};
+
+pub struct Implementation<M> {
+    pub name: &'static str,
+    pub solve: fn(&M) -> Result<Answer>,
+}
+
+impl<M> Implementation<M> {
+    pub fn new(name: &'static str, solve: fn(&M) -> Result<Answer>) -> Self {
+        Implementation { name, solve }
+    }
+}
+
+/// A [`Solution`] with more than one way to answer its parts.
+pub trait Differential: Solution {
+    /// Implementations of `part` to check against the reference one
+    /// ([`Solution::part1`] or [`Solution::part2`]).
+    fn alternatives(part: Part) -> Vec<Implementation<Self::Model>>;
+
+    /// A random, well-formed puzzle input; `size` grows from 1 to
+    /// [`Config::max_size`] over a run.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+
+    /// Whether a model still satisfies the guarantees of the puzzle, so that
+    /// shrinking does not wander off to inputs no implementation supports.
+    fn is_valid(_model: &Self::Model) -> bool {
+        true
+    }
+
+    /// Smaller variants of `input` to try while minimizing a counterexample.
+    fn shrink(input: &str) -> Vec<String> {
+        shrink_text(input)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+    pub max_size: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            cases: 100,
+            seed: 0,
+            max_size: 20,
+        }
+    }
+}
+
+/// What an implementation returned, or why it did not.
+pub type Outcome = std::result::Result<Answer, String>;
+
+#[derive(Debug, Clone)]
+pub struct Disagreement {
+    pub day: u8,
+    pub part: Part,
+    pub seed: u64,
+    pub case: usize,
+    pub input: String,
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "day {:02} part {}: implementations disagree (seed {}, case {})",
+            self.day, self.part, self.seed, self.case
+        )?;
+        writeln!(f, "minimized input:")?;
+        for line in self.input.lines() {
+            writeln!(f, "    {}", line)?;
+        }
+        for (name, outcome) in &self.outcomes {
+            match outcome {
+                Ok(answer) => writeln!(f, "  {}: {}", name, answer)?,
+                Err(error) => writeln!(f, "  {}: error: {}", name, error)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn implementations<S: Differential>(part: Part) -> Vec<Implementation<S::Model>> {
+    let reference = match part {
+        Part::One => Implementation::new("reference", S::part1),
+        Part::Two => Implementation::new("reference", S::part2),
+    };
+    iter::once(reference).chain(S::alternatives(part)).collect()
+}
+
+pub fn names<S: Differential>(part: Part) -> Vec<&'static str> {
+    implementations::<S>(part).iter().map(|i| i.name).collect()
+}
+
+/// Run `config.cases` generated inputs through every implementation of
+/// `part`, returning the first disagreement once minimized.
+///
+/// Fails if the generator produces an input that does not parse or is not
+/// valid, since nothing can be concluded from it.
+pub fn check<S: Differential>(part: Part, config: &Config) -> Result<Option<Disagreement>> {
+    let implementations = implementations::<S>(part);
+    if implementations.len() < 2 {
+        return Ok(None);
+    }
+
+    for case in 0..config.cases {
+        let mut rng = Rng::new(config.seed ^ (case as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let size = 1 + case * config.max_size / config.cases;
+        let input = S::generate(&mut rng, size);
+        let outcomes = evaluate::<S>(&implementations, &input).ok_or_else(|| {
+            anyhow!(
+                "day {:02}: generated input is not valid (seed {}, case {}):\n{}",
+                S::DAY,
+                config.seed,
+                case,
+                input
+            )
+        })?;
+
+        if let Some(kind) = classify(&outcomes) {
+            let input = minimize::<S>(&implementations, input, kind);
+            let outcomes =
+                evaluate::<S>(&implementations, &input).expect("minimized input stays valid");
+            return Ok(Some(Disagreement {
+                day: S::DAY,
+                part,
+                seed: config.seed,
+                case,
+                input,
+                outcomes: implementations
+                    .iter()
+                    .map(|i| i.name)
+                    .zip(outcomes)
+                    .collect(),
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// Panic with a report unless every part agrees on `config.cases` inputs.
+pub fn assert_agree<S: Differential>(config: &Config) {
+    for part in Part::ALL {
+        match check::<S>(part, config) {
+            Ok(None) => {}
+            Ok(Some(disagreement)) => panic!("{}", disagreement),
+            Err(error) => panic!("{:#}", error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// Every implementation answered, with different answers.
+    Answers,
+    /// Some implementations failed where others did not.
+    Failures,
+}
+
+fn classify(outcomes: &[Outcome]) -> Option<Kind> {
+    if outcomes.iter().all(|o| o.is_err()) || outcomes.windows(2).all(|w| w[0] == w[1]) {
+        None
+    } else if outcomes.iter().all(|o| o.is_ok()) {
+        Some(Kind::Answers)
+    } else {
+        Some(Kind::Failures)
+    }
+}
+
+/// Outcomes of every implementation, or `None` if `input` is not valid.
+fn evaluate<S: Differential>(
+    implementations: &[Implementation<S::Model>],
+    input: &str,
+) -> Option<Vec<Outcome>> {
+    let model = catch(|| S::parse(input)).ok()?.ok()?;
+    if !catch(|| S::is_valid(&model)).unwrap_or(false) {
+        return None;
+    }
+    let outcomes = implementations
+        .iter()
+        .map(|i| match catch(|| (i.solve)(&model)) {
+            Ok(Ok(answer)) => Ok(answer),
+            Ok(Err(error)) => Err(format!("{:#}", error)),
+            Err(panic) => Err(format!("panicked: {}", panic)),
+        })
+        .collect();
+    Some(outcomes)
+}
+
+const SHRINK_BUDGET: usize = 10_000;
+
+fn minimize<S: Differential>(
+    implementations: &[Implementation<S::Model>],
+    mut input: String,
+    kind: Kind,
+) -> String {
+    let mut budget = SHRINK_BUDGET;
+    'shrink: loop {
+        for candidate in S::shrink(&input) {
+            if budget == 0 {
+                break 'shrink;
+            }
+            budget -= 1;
+            let outcomes = evaluate::<S>(implementations, &candidate);
+            if outcomes.is_some_and(|o| classify(&o) == Some(kind)) {
+                input = candidate;
+                continue 'shrink;
+            }
+        }
+        break;
+    }
+    input
+}
+
+/// Generic shrinking steps: drop a line, drop a word, or make a number smaller.
+pub fn shrink_text(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let rejoin = |lines: &[String]| {
+        let mut text = lines.join("\n");
+        if input.ends_with('\n') {
+            text.push('\n');
+        }
+        text
+    };
+    let mut candidates = Vec::new();
+
+    for i in 0..lines.len() {
+        let mut rest: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        rest.remove(i);
+        candidates.push(rejoin(&rest));
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.len() < 2 {
+            continue;
+        }
+        for j in 0..words.len() {
+            let mut rest: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+            rest[i] = words
+                .iter()
+                .enumerate()
+                .filter(|&(k, _)| k != j)
+                .map(|(_, w)| *w)
+                .collect::<Vec<_>>()
+                .join(" ");
+            candidates.push(rejoin(&rest));
+        }
+    }
+
+    for digits in number_spans(input) {
+        let Ok(n) = input[digits.clone()].parse::<u64>() else {
+            continue;
+        };
+        let mut smaller = vec![0, n / 2, n.saturating_sub(1)];
+        smaller.dedup();
+        for m in smaller.into_iter().filter(|&m| m < n) {
+            candidates.push(format!(
+                "{}{}{}",
+                &input[..digits.start],
+                m,
+                &input[digits.end..]
+            ));
+        }
+    }
+
+    candidates
+}
+
+fn number_spans(input: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in input.char_indices().chain(iter::once((input.len(), ' '))) {
+        match (start, c.is_ascii_digit()) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                spans.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f`, turning a panic into its message. Panics are not reported by the
+/// panic hook meanwhile, as shrinking can trigger thousands of them.
+pub fn catch<T>(f: impl FnOnce() -> T) -> std::result::Result<T, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                previous(info)
+            }
+        }));
+    });
+
+    QUIET.with(|quiet| quiet.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.with(|quiet| quiet.set(false));
+    result.map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+/// SplitMix64: small, deterministic, and good enough to generate inputs that
+/// can be replayed from their seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "empty range");
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.range(0..n) == 0
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..i as u64 + 1) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        type Model = Vec<u64>;
+
+        fn parse(input: &str) -> Result<Self::Model> {
+            Ok(input
+                .split_whitespace()
+                .map(|n| n.parse())
+                .collect::<std::result::Result<_, _>>()?)
+        }
+
+        fn part1(model: &Self::Model) -> Result<Answer> {
+            Ok(model.iter().sum::<u64>().into())
+        }
+    }
+
+    impl Differential for Sum {
+        fn alternatives(part: Part) -> Vec<Implementation<Self::Model>> {
+            match part {
+                Part::One => vec![
+                    Implementation::new("reduce", |model| {
+                        Ok(model
+                            .iter()
+                            .copied()
+                            .reduce(|a, b| a + b)
+                            .unwrap_or(0)
+                            .into())
+                    }),
+                    Implementation::new("skips large", |model| {
+                        Ok(model.iter().filter(|&&n| n < 50).sum::<u64>().into())
+                    }),
+                ],
+                Part::Two => vec![],
+            }
+        }
+
+        fn generate(rng: &mut Rng, size: usize) -> String {
+            (0..size)
+                .map(|_| rng.range(0..100).to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+
+    #[test]
+    fn test_shrink_text() {
+        let candidates = shrink_text("a 12\nb\n");
+        assert!(candidates.contains(&"b\n".to_string()));
+        assert!(candidates.contains(&"a 12\n".to_string()));
+        assert!(candidates.contains(&"12\nb\n".to_string()));
+        assert!(candidates.contains(&"a 6\nb\n".to_string()));
+        assert!(candidates.contains(&"a 0\nb\n".to_string()));
+        assert!(candidates.contains(&"a 11\nb\n".to_string()));
+    }
+
+    #[test]
+    fn test_check_minimizes() {
+        let disagreement = check::<Sum>(Part::One, &Config::default())
+            .unwrap()
+            .expect("a disagreement");
+        assert_eq!(disagreement.input, "50");
+        assert_eq!(
+            disagreement.outcomes,
+            vec![
+                ("reference", Ok(Answer::Int(50))),
+                ("reduce", Ok(Answer::Int(50))),
+                ("skips large", Ok(Answer::Int(0))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_single_implementation() {
+        assert!(check::<Sum>(Part::Two, &Config::default())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_rng_is_deterministic() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..10 {
+            assert_eq!(a.range(3..9), b.range(3..9));
+        }
+    }
+}
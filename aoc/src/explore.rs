@@ -0,0 +1,40 @@
+//! Queries on parsed models, for the runner's REPL.
+
+use anyhow::{bail, Context, Result};
+
+use crate::Solution;
+
+/// A query understood by a day's REPL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Command {
+    pub name: &'static str,
+    /// Arguments, as shown in the help.
+    pub args: &'static str,
+    pub help: &'static str,
+}
+
+impl Command {
+    pub const fn new(name: &'static str, args: &'static str, help: &'static str) -> Self {
+        Command { name, args, help }
+    }
+}
+
+/// A [`Solution`] whose model can be queried interactively.
+pub trait Explore: Solution {
+    fn commands() -> Vec<Command>;
+
+    /// Answer `command`, one of [`Explore::commands`], called with `args`.
+    fn query(model: &Self::Model, command: &str, args: &[&str]) -> Result<String>;
+}
+
+/// The `index`th argument of a command, parsed.
+pub fn arg<T: std::str::FromStr>(args: &[&str], index: usize, name: &str) -> Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let Some(arg) = args.get(index) else {
+        bail!("missing argument <{}>", name);
+    };
+    arg.parse()
+        .with_context(|| format!("invalid <{}> {:?}", name, arg))
+}
@@ -0,0 +1,63 @@
+//! Entry points for the fuzz targets in `fuzz/`, run with
+//! `cargo +nightly fuzz run day_NN` from the repository root.
+//!
+//! Inputs that once crashed a target are kept in
+//! `fuzz/regressions/day_NN/`, named after the bug, and replayed by the
+//! tests of the day with [`assert_regressions`].
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{diff, Solution};
+
+/// A [`Solution`] whose model can be exercised on any input that parses.
+pub trait Fuzz: Solution {
+    /// Run the model's operations. Errors are fine, panics are bugs; it must
+    /// stay fast, so operations that may not terminate are left out.
+    fn exercise(model: &Self::Model);
+}
+
+pub fn run<S: Fuzz>(data: &[u8]) {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(model) = S::parse(input) {
+        S::exercise(&model);
+    }
+}
+
+/// Directory of the inputs that once crashed the target of `day`, relative
+/// to the day's crate.
+pub fn regressions_dir(day: u8) -> PathBuf {
+    PathBuf::from("..")
+        .join("fuzz")
+        .join("regressions")
+        .join(format!("day_{:02}", day))
+}
+
+/// Replay the regressions of `S` through its target, for the tests of its
+/// crate; panics listing those that still crash.
+pub fn assert_regressions<S: Fuzz>() {
+    let dir = regressions_dir(S::DAY);
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("failed to list {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no regressions in {}", dir.display());
+    let failed: Vec<String> = paths
+        .iter()
+        .filter_map(|path| {
+            let data = fs::read(path).unwrap();
+            let message = diff::catch(|| run::<S>(&data)).err()?;
+            Some(format!("{}: {}", path.display(), message))
+        })
+        .collect();
+    assert!(
+        failed.is_empty(),
+        "{} of {} regressions crash:\n{}",
+        failed.len(),
+        paths.len(),
+        failed.join("\n")
+    );
+}
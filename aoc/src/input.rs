@@ -0,0 +1,237 @@
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use flate2::bufread::MultiGzDecoder;
+
+use crate::crypt::{self, Key};
+
+/// The `.txt` files of every `day-NN/data`, by path relative to the repository
+/// root, when built with the `embed` feature.
+const EMBEDDED: &[(&str, &[u8])] = include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+
+/// Directory holding a day's inputs, relative to the repository root.
+pub fn data_dir(day: u8) -> PathBuf {
+    PathBuf::from(format!("day-{:02}", day)).join("data")
+}
+
+/// The copy of a file compiled in with the `embed` feature, e.g. of
+/// `day-05/data/input.txt`, encrypted if the file was.
+pub fn embedded(path: impl AsRef<Path>) -> Option<&'static [u8]> {
+    let path = path.as_ref();
+    EMBEDDED
+        .iter()
+        .find(|(name, _)| Path::new(name) == path)
+        .map(|(_, content)| *content)
+}
+
+/// How an input file is compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    const ALL: [Compression; 2] = [Compression::Gzip, Compression::Zstd];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Compression::Gzip => "gz",
+            Compression::Zstd => "zst",
+        }
+    }
+
+    fn magic(self) -> &'static [u8] {
+        match self {
+            Compression::Gzip => &[0x1f, 0x8b],
+            Compression::Zstd => &[0x28, 0xb5, 0x2f, 0xfd],
+        }
+    }
+
+    /// The compression of the file at `path` whose data starts with `start`,
+    /// from its magic bytes, else from its extension.
+    pub fn detect(path: &Path, start: &[u8]) -> Option<Self> {
+        let mut all = Compression::ALL.into_iter();
+        all.clone()
+            .find(|c| start.starts_with(c.magic()))
+            .or_else(|| all.find(|c| path.extension().is_some_and(|e| e == c.extension())))
+    }
+
+    fn decoder(self, reader: Box<dyn BufRead>) -> Result<Box<dyn BufRead>> {
+        Ok(match self {
+            Compression::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(reader))),
+            Compression::Zstd => Box::new(BufReader::new(zstd::Decoder::with_buffer(reader)?)),
+        })
+    }
+}
+
+/// `path` without the extension of its compression, if any, e.g.
+/// `big.txt` for `big.txt.gz`.
+pub fn uncompressed(path: &Path) -> PathBuf {
+    match path.extension() {
+        Some(e) if Compression::ALL.iter().any(|c| e == c.extension()) => path.with_extension(""),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Read an input, from its embedded copy if there is one, so that binaries
+/// built with `embed` run from any directory. Encrypted inputs are decrypted,
+/// see [`crypt`], then compressed ones decompressed, see [`Compression`].
+pub fn read(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let data = match embedded(path) {
+        Some(data) => data.to_vec(),
+        None => fs::read(path).with_context(|| format!("failed to read {}", path.display()))?,
+    };
+    decode(path, data, Key::load)
+}
+
+/// Open an input to be read line by line, from its embedded copy if there is
+/// one, decompressing it on the fly. Only encrypted inputs are read whole, to
+/// be decrypted.
+pub fn open(path: impl AsRef<Path>) -> Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    let context = || format!("failed to read {}", path.display());
+    let mut reader: Box<dyn BufRead> = match embedded(path) {
+        Some(data) => Box::new(data),
+        None => Box::new(BufReader::new(
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?,
+        )),
+    };
+    if crypt::is_encrypted(reader.fill_buf().with_context(context)?) {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).with_context(context)?;
+        reader = Box::new(Cursor::new(decrypt(path, data, Key::load)?));
+    }
+    match Compression::detect(path, reader.fill_buf().with_context(context)?) {
+        Some(compression) => compression.decoder(reader).with_context(context),
+        None => Ok(reader),
+    }
+}
+
+/// The text of the input read from `path`, decrypted with `key` and
+/// decompressed if need be.
+fn decode(path: &Path, data: Vec<u8>, key: impl FnOnce() -> Result<Key>) -> Result<String> {
+    let data = decrypt(path, data, key)?;
+    let data = match Compression::detect(path, &data) {
+        Some(compression) => {
+            let mut text = Vec::new();
+            compression
+                .decoder(Box::new(Cursor::new(data)))
+                .and_then(|mut decoder| Ok(decoder.read_to_end(&mut text)?))
+                .with_context(|| format!("failed to decompress {}", path.display()))?;
+            text
+        }
+        None => data,
+    };
+    String::from_utf8(data).with_context(|| format!("{} is not UTF-8", path.display()))
+}
+
+fn decrypt(path: &Path, data: Vec<u8>, key: impl FnOnce() -> Result<Key>) -> Result<Vec<u8>> {
+    match crypt::is_encrypted(&data) {
+        true => key()
+            .and_then(|key| key.decrypt(&data))
+            .with_context(|| format!("failed to decrypt {}", path.display())),
+        false => Ok(data),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded() {
+        let path = data_dir(4).join("test_input.txt");
+        assert_eq!(embedded(&path).is_some(), cfg!(feature = "embed"));
+        if let Some(content) = embedded(&path) {
+            assert_eq!(content, fs::read(Path::new("..").join(&path)).unwrap());
+        }
+        assert_eq!(embedded("day-04/data/missing.txt"), None);
+    }
+
+    #[test]
+    fn test_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.txt");
+        fs::write(&path, "Time: 7 15 30\nDistance: 9 40 200\n").unwrap();
+        let lines: Vec<String> = open(&path)
+            .unwrap()
+            .lines()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(lines, ["Time: 7 15 30", "Distance: 9 40 200"]);
+        assert!(open(dir.path().join("missing.txt")).is_err());
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+        std::io::Write::write_all(&mut encoder, data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_compression() {
+        let text = "Time: 7 15 30\nDistance: 9 40 200\n";
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            ("input.txt.gz", gzip(text.as_bytes())),
+            (
+                "input.txt.zst",
+                zstd::encode_all(text.as_bytes(), 0).unwrap(),
+            ),
+            ("gzip.txt", gzip(text.as_bytes())),
+        ];
+        for (name, data) in files {
+            let path = dir.path().join(name);
+            fs::write(&path, data).unwrap();
+            assert_eq!(read(&path).unwrap(), text, "{}", name);
+            let mut streamed = String::new();
+            open(&path).unwrap().read_to_string(&mut streamed).unwrap();
+            assert_eq!(streamed, text, "{}", name);
+        }
+
+        let path = dir.path().join("plain.txt.gz");
+        fs::write(&path, text).unwrap();
+        assert_eq!(
+            read(&path).unwrap_err().to_string(),
+            format!("failed to decompress {}", path.display())
+        );
+
+        let path = Path::new("big.txt.zst");
+        assert_eq!(Compression::detect(path, b""), Some(Compression::Zstd));
+        assert_eq!(
+            Compression::detect(Path::new("a.txt"), b"\x1f\x8b"),
+            Some(Compression::Gzip)
+        );
+        assert_eq!(Compression::detect(Path::new("a.txt"), b"Time"), None);
+        assert_eq!(uncompressed(path), Path::new("big.txt"));
+        assert_eq!(uncompressed(Path::new("big.txt")), Path::new("big.txt"));
+    }
+
+    #[test]
+    fn test_encrypted_compression() {
+        let path = Path::new("input.txt.gz");
+        let key = Key::generate();
+        let sealed = key.encrypt(&gzip(b"Time: 7 15 30"));
+        let decoded = decode(path, sealed, || Ok(key.clone())).unwrap();
+        assert_eq!(decoded, "Time: 7 15 30");
+    }
+
+    #[test]
+    fn test_decode() {
+        let path = Path::new("input.txt");
+        let key = Key::generate();
+        let sealed = key.encrypt(b"Time: 7 15 30");
+        let decoded = decode(path, sealed.clone(), || Ok(key.clone())).unwrap();
+        assert_eq!(decoded, "Time: 7 15 30");
+        let error = decode(path, sealed, || Ok(Key::generate())).unwrap_err();
+        assert_eq!(error.to_string(), "failed to decrypt input.txt");
+
+        let plain = decode(path, b"Time: 7".to_vec(), || unreachable!("no key needed"));
+        assert_eq!(plain.unwrap(), "Time: 7");
+        assert!(decode(path, vec![0xff], || unreachable!()).is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use std::any::Any;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+
+pub use answer::Answer;
+
+pub mod anonymize;
+mod answer;
+pub mod crypt;
+pub mod diff;
+pub mod explore;
+pub mod fuzz;
+pub mod input;
+pub mod num;
+pub mod parallel;
+pub mod spec;
+pub mod stream;
+pub mod trace;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part {:?}, expected 1 or 2", s)),
+        }
+    }
+}
+
+/// Error returned by a part that has not been solved yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unimplemented;
+
+impl fmt::Display for Unimplemented {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not implemented")
+    }
+}
+
+impl std::error::Error for Unimplemented {}
+
+/// A day's puzzle: how to parse its input into a model, and how to answer
+/// both parts from that model.
+pub trait Solution {
+    const DAY: u8;
+    type Model;
+
+    fn parse(input: &str) -> Result<Self::Model>;
+
+    fn part1(_model: &Self::Model) -> Result<Answer> {
+        Err(Unimplemented.into())
+    }
+
+    fn part2(_model: &Self::Model) -> Result<Answer> {
+        Err(Unimplemented.into())
+    }
+
+    fn solve(model: &Self::Model, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => Self::part1(model),
+            Part::Two => Self::part2(model),
+        }
+    }
+}
+
+/// Solve both parts of `S` on `data/input.txt`, or its embedded copy, one
+/// `Part N: answer` line each; the `main` of every day's binary.
+pub fn main<S: Solution>() -> Result<()> {
+    let embedded = input::data_dir(S::DAY).join("input.txt");
+    let input = match input::embedded(&embedded) {
+        Some(_) => input::read(embedded)?,
+        None => input::read("data/input.txt")?,
+    };
+    let model = S::parse(&input)?;
+    for part in Part::ALL {
+        match S::solve(&model, part) {
+            Ok(answer) => println!("Part {}: {}", part, answer),
+            Err(error) => println!("Part {}: {:#}", part, error),
+        }
+    }
+    Ok(())
+}
+
+/// A parsed puzzle input, whose concrete type is only known to its day.
+pub struct Model(Box<dyn Any>);
+
+type Check = fn(Part, &diff::Config) -> Result<Option<diff::Disagreement>>;
+
+type Anonymizer = fn(&str, &mut diff::Rng) -> Result<anonymize::Anonymized>;
+
+type Streamer = fn(&mut dyn BufRead, Part) -> Result<Answer>;
+
+#[cfg(feature = "serde")]
+type Dump = fn(&Model) -> Result<serde_json::Value>;
+
+#[derive(Clone, Copy)]
+struct Explorer {
+    commands: fn() -> Vec<explore::Command>,
+    query: fn(&Model, &str, &[&str]) -> Result<String>,
+}
+
+/// A type-erased handle on a day's [`Solution`], as registered in the runner.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    parse: fn(&str) -> Result<Model>,
+    solve: fn(&Model, Part) -> Result<Answer>,
+    implementations: fn(Part) -> Vec<&'static str>,
+    check: Option<Check>,
+    explorer: Option<Explorer>,
+    anonymizer: Option<Anonymizer>,
+    numbers: Option<fn(Day, num::Numbers) -> Day>,
+    stream: Option<Streamer>,
+    #[cfg(feature = "serde")]
+    dump: Option<Dump>,
+}
+
+impl Day {
+    pub fn new<S: Solution>() -> Self
+    where
+        S::Model: 'static,
+    {
+        Day {
+            day: S::DAY,
+            parse: parse::<S>,
+            solve: solve::<S>,
+            implementations: |_| vec!["reference"],
+            check: None,
+            explorer: None,
+            anonymizer: None,
+            numbers: None,
+            stream: None,
+            #[cfg(feature = "serde")]
+            dump: None,
+        }
+    }
+
+    /// A day whose parts have alternative implementations to cross-check.
+    pub fn differential<S: diff::Differential>() -> Self
+    where
+        S::Model: 'static,
+    {
+        Day {
+            implementations: diff::names::<S>,
+            check: Some(diff::check::<S>),
+            ..Day::new::<S>()
+        }
+    }
+
+    /// Let the model of this day, which must be `S`'s, be queried in the REPL.
+    pub fn explorable<S: explore::Explore>(self) -> Self
+    where
+        S::Model: 'static,
+    {
+        assert_eq!(self.day, S::DAY, "explorer of another day");
+        Day {
+            explorer: Some(Explorer {
+                commands: S::commands,
+                query: query::<S>,
+            }),
+            ..self
+        }
+    }
+
+    /// Let the inputs of this day, which must be `S`'s, be anonymized.
+    pub fn anonymizable<S: anonymize::Anonymize>(self) -> Self {
+        assert_eq!(self.day, S::DAY, "anonymizer of another day");
+        Day {
+            anonymizer: Some(S::anonymize),
+            ..self
+        }
+    }
+
+    /// Let this day, which must be `S`, solve with other integers.
+    pub fn numeric<S: num::Numeric>(self) -> Self {
+        assert_eq!(self.day, S::DAY, "numbers of another day");
+        Day {
+            numbers: Some(num::with_numbers::<S>),
+            ..self
+        }
+    }
+
+    /// Let this day, which must be `S`, solve inputs read line by line.
+    pub fn streamable<S: stream::Stream>(self) -> Self {
+        assert_eq!(self.day, S::DAY, "stream of another day");
+        Day {
+            stream: Some(S::solve_stream),
+            ..self
+        }
+    }
+
+    /// This day solving with `N`, which takes the input itself as model, and
+    /// reports panics such as overflows as errors.
+    fn solving_with<S: num::Numeric, N: num::Int>(self) -> Self {
+        Day {
+            parse: |input| Ok(Model(Box::new(input.to_string()))),
+            solve: solve_with::<S, N>,
+            implementations: |_| vec![N::NAME],
+            check: None,
+            explorer: None,
+            anonymizer: None,
+            numbers: None,
+            stream: None,
+            #[cfg(feature = "serde")]
+            dump: None,
+            ..self
+        }
+    }
+
+    /// Let the model of this day, which must be `S`'s, be serialized.
+    #[cfg(feature = "serde")]
+    pub fn dumpable<S: Solution>(self) -> Self
+    where
+        S::Model: serde::Serialize + 'static,
+    {
+        assert_eq!(self.day, S::DAY, "model of another day");
+        Day {
+            dump: Some(dump::<S>),
+            ..self
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Model> {
+        (self.parse)(input)
+    }
+
+    pub fn solve(&self, model: &Model, part: Part) -> Result<Answer> {
+        (self.solve)(model, part)
+    }
+
+    pub fn implementations(&self, part: Part) -> Vec<&'static str> {
+        (self.implementations)(part)
+    }
+
+    /// Cross-check the implementations of `part`, or `None` if the day
+    /// registers no alternatives.
+    pub fn check(
+        &self,
+        part: Part,
+        config: &diff::Config,
+    ) -> Option<Result<Option<diff::Disagreement>>> {
+        self.check.map(|check| check(part, config))
+    }
+
+    /// Queries on the model, empty if the day is not explorable.
+    pub fn commands(&self) -> Vec<explore::Command> {
+        self.explorer.map_or(Vec::new(), |e| (e.commands)())
+    }
+
+    /// The model as a JSON value, or `None` if the day is not dumpable.
+    #[cfg(feature = "serde")]
+    pub fn dump(&self, model: &Model) -> Option<Result<serde_json::Value>> {
+        self.dump.map(|dump| dump(model))
+    }
+
+    /// An equivalent of `input` that can be shared, drawn from `seed`.
+    pub fn anonymize(&self, input: &str, seed: u64) -> Result<anonymize::Anonymized> {
+        match self.anonymizer {
+            Some(anonymize) => anonymize(input, &mut diff::Rng::new(seed)),
+            None => bail!("day {} has no anonymizer", self.day),
+        }
+    }
+
+    /// This day solving with `numbers`, whose models are then the inputs.
+    pub fn with_numbers(&self, numbers: num::Numbers) -> Result<Day> {
+        match self.numbers {
+            Some(with_numbers) => Ok(with_numbers(*self, numbers)),
+            None => bail!("day {} only solves with its own integers", self.day),
+        }
+    }
+
+    /// Whether the day solves inputs read line by line.
+    pub fn streams(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Solve `part` reading `input` line by line, see [`stream`].
+    pub fn solve_stream(&self, input: &mut dyn BufRead, part: Part) -> Result<Answer> {
+        match self.stream {
+            Some(solve) => solve(input, part),
+            None => bail!("day {} cannot stream its input", self.day),
+        }
+    }
+
+    pub fn query(&self, model: &Model, command: &str, args: &[&str]) -> Result<String> {
+        match self.explorer {
+            Some(explorer) => (explorer.query)(model, command, args),
+            None => bail!("day {} has no queries", self.day),
+        }
+    }
+}
+
+fn parse<S: Solution>(input: &str) -> Result<Model>
+where
+    S::Model: 'static,
+{
+    Ok(Model(Box::new(S::parse(input)?)))
+}
+
+fn downcast<S: Solution>(model: &Model) -> &S::Model
+where
+    S::Model: 'static,
+{
+    model
+        .0
+        .downcast_ref::<S::Model>()
+        .expect("model parsed by another day")
+}
+
+fn solve<S: Solution>(model: &Model, part: Part) -> Result<Answer>
+where
+    S::Model: 'static,
+{
+    S::solve(downcast::<S>(model), part)
+}
+
+fn solve_with<S: num::Numeric, N: num::Int>(model: &Model, part: Part) -> Result<Answer> {
+    let input = model
+        .0
+        .downcast_ref::<String>()
+        .expect("model parsed by another day");
+    diff::catch(|| S::solve_with::<N>(input, part)).unwrap_or_else(|message| bail!(message))
+}
+
+fn query<S: explore::Explore>(model: &Model, command: &str, args: &[&str]) -> Result<String>
+where
+    S::Model: 'static,
+{
+    S::query(downcast::<S>(model), command, args)
+}
+
+#[cfg(feature = "serde")]
+fn dump<S: Solution>(model: &Model) -> Result<serde_json::Value>
+where
+    S::Model: serde::Serialize + 'static,
+{
+    Ok(serde_json::to_value(downcast::<S>(model))?)
+}
@@ -0,0 +1,319 @@
+//! Integers that solvers can be generic over, so that a day runs with `i64`,
+//! `i128` or arbitrary precision, and optionally checks for overflows.
+//!
+//! Operators on [`Checked`] integers panic naming the operation that
+//! overflowed, e.g. `i64 overflow: 9223372036854775807 + 1`, where plain
+//! integers panic without operands in debug builds and wrap in release ones.
+
+use std::fmt;
+use std::hash::Hash;
+use std::iter::Sum;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+pub use num_bigint::BigInt;
+
+use crate::{Answer, Day, Part, Solution};
+
+pub trait Int:
+    Clone
+    + Ord
+    + Hash
+    + fmt::Debug
+    + fmt::Display
+    + Send
+    + Sync
+    + 'static
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Sum
+{
+    /// The name of the type, e.g. `i64`.
+    const NAME: &'static str;
+
+    /// Parse a decimal number, failing if it does not fit.
+    fn parse(text: &str) -> Result<Self>;
+
+    fn from_i64(n: i64) -> Self;
+
+    /// The value, unless it does not fit an `i64`.
+    fn to_i64(&self) -> Option<i64>;
+
+    fn into_answer(self) -> Answer;
+
+    /// The sum, unless it does not fit the type, for solvers to report
+    /// overflows of their inputs as errors.
+    fn try_add(&self, rhs: &Self) -> Option<Self>;
+
+    fn zero() -> Self {
+        Self::from_i64(0)
+    }
+
+    fn one() -> Self {
+        Self::from_i64(1)
+    }
+}
+
+/// Integers of fixed width, whose operations can be checked.
+pub trait Primitive: Int + Copy {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! primitive {
+    ($($t:ty),*) => {
+        $(
+            impl Int for $t {
+                const NAME: &'static str = stringify!($t);
+
+                fn parse(text: &str) -> Result<Self> {
+                    text.parse()
+                        .with_context(|| format!("invalid {} {:?}", Self::NAME, text))
+                }
+
+                fn from_i64(n: i64) -> Self {
+                    n.try_into().expect("fits")
+                }
+
+                fn to_i64(&self) -> Option<i64> {
+                    (*self).try_into().ok()
+                }
+
+                fn into_answer(self) -> Answer {
+                    self.into()
+                }
+
+                fn try_add(&self, rhs: &Self) -> Option<Self> {
+                    self.checked_add(*rhs)
+                }
+            }
+
+            impl Primitive for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+primitive!(i64, i128);
+
+impl Int for BigInt {
+    const NAME: &'static str = "big";
+
+    fn parse(text: &str) -> Result<Self> {
+        BigInt::from_str(text).with_context(|| format!("invalid integer {:?}", text))
+    }
+
+    fn from_i64(n: i64) -> Self {
+        n.into()
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        self.try_into().ok()
+    }
+
+    fn into_answer(self) -> Answer {
+        self.into()
+    }
+
+    fn try_add(&self, rhs: &Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+}
+
+/// An integer whose operations panic on overflow, naming the operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<T>(pub T);
+
+impl<T: Primitive> Checked<T> {
+    fn apply(self, op: &str, rhs: Self, f: fn(T, T) -> Option<T>) -> Self {
+        match f(self.0, rhs.0) {
+            Some(n) => Checked(n),
+            None => panic!("{} overflow: {} {} {}", T::NAME, self.0, op, rhs.0),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Primitive> Add for Checked<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.apply("+", rhs, T::checked_add)
+    }
+}
+
+impl<T: Primitive> Sub for Checked<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.apply("-", rhs, T::checked_sub)
+    }
+}
+
+impl<T: Primitive> Mul for Checked<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.apply("*", rhs, T::checked_mul)
+    }
+}
+
+impl<T: Primitive> Sum for Checked<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<T: Primitive> Int for Checked<T> {
+    const NAME: &'static str = T::NAME;
+
+    fn parse(text: &str) -> Result<Self> {
+        T::parse(text).map(Checked)
+    }
+
+    fn from_i64(n: i64) -> Self {
+        Checked(T::from_i64(n))
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+
+    fn into_answer(self) -> Answer {
+        self.0.into_answer()
+    }
+
+    fn try_add(&self, rhs: &Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Checked)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Width {
+    #[default]
+    I64,
+    I128,
+    /// Arbitrary precision.
+    Big,
+}
+
+impl FromStr for Width {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "i64" => Ok(Width::I64),
+            "i128" => Ok(Width::I128),
+            "big" => Ok(Width::Big),
+            _ => Err(format!("invalid width {:?}, expected i64, i128 or big", s)),
+        }
+    }
+}
+
+impl fmt::Display for Width {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Width::I64 => "i64",
+            Width::I128 => "i128",
+            Width::Big => "big",
+        })
+    }
+}
+
+/// The integers a day solves with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Numbers {
+    pub width: Width,
+    /// Whether overflows are reported; arbitrary precision never overflows.
+    pub checked: bool,
+}
+
+/// A [`Solution`] that can solve with any [`Int`].
+pub trait Numeric: Solution {
+    /// Solve `part` of `input`, computing with `N`.
+    fn solve_with<N: Int>(input: &str, part: Part) -> Result<Answer>;
+}
+
+/// `day`, solving with `numbers` through `S`.
+pub(crate) fn with_numbers<S: Numeric>(day: Day, numbers: Numbers) -> Day {
+    match (numbers.width, numbers.checked) {
+        (Width::I64, false) => day.solving_with::<S, i64>(),
+        (Width::I64, true) => day.solving_with::<S, Checked<i64>>(),
+        (Width::I128, false) => day.solving_with::<S, i128>(),
+        (Width::I128, true) => day.solving_with::<S, Checked<i128>>(),
+        (Width::Big, _) => day.solving_with::<S, BigInt>(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff;
+
+    #[test]
+    fn test_checked() {
+        let max = Checked(i64::MAX);
+        assert_eq!(max - Checked(1) + Checked(1), max);
+        let message = diff::catch(|| max + Checked(1)).unwrap_err();
+        assert_eq!(message, "i64 overflow: 9223372036854775807 + 1");
+        let message = diff::catch(|| Checked(i128::MIN) - Checked(1)).unwrap_err();
+        assert!(message.starts_with("i128 overflow: -1701"), "{}", message);
+        let message = diff::catch(|| [max, max].into_iter().sum::<Checked<i64>>()).unwrap_err();
+        assert_eq!(
+            message,
+            "i64 overflow: 9223372036854775807 + 9223372036854775807"
+        );
+        assert_eq!(Checked(3_i64) * Checked(4), Checked(12));
+        assert_eq!(max.try_add(&Checked(1)), None);
+        assert_eq!(i64::MAX.try_add(&-1), Some(i64::MAX - 1));
+        assert_eq!(
+            BigInt::from(i64::MAX).try_add(&BigInt::from(1)),
+            Some(BigInt::from(1_u64 << 63))
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(<i64 as Int>::parse("-12").unwrap(), -12);
+        let error = <i64 as Int>::parse("9223372036854775808").unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            "invalid i64 \"9223372036854775808\": number too large to fit in target type"
+        );
+        assert_eq!(
+            <i128 as Int>::parse("9223372036854775808").unwrap(),
+            1 << 63
+        );
+        let big = <BigInt as Int>::parse("99999999999999999999999").unwrap();
+        assert_eq!(big.to_i64(), None);
+        assert_eq!(big.into_answer().to_string(), "99999999999999999999999");
+        assert_eq!(<Checked<i64> as Int>::NAME, "i64");
+    }
+
+    #[test]
+    fn test_width() {
+        for width in [Width::I64, Width::I128, Width::Big] {
+            assert_eq!(width.to_string().parse::<Width>().unwrap(), width);
+        }
+        assert!("i32".parse::<Width>().is_err());
+    }
+}
@@ -0,0 +1,52 @@
+//! Iterating in parallel with rayon when the calling day is built with its
+//! `parallel` feature, else sequentially, with the same answers either way.
+//!
+//! The macros expand to `rayon`'s parallel iterators or to the standard ones,
+//! so the code consuming them must only use the adapters both provide, e.g.
+//! `map`, `filter`, `sum`, `min`, `count` and `collect`. A day opting in
+//! declares the feature and the optional dependency:
+//!
+//! ```toml
+//! [dependencies]
+//! rayon = { version = "1.8.0", optional = true }
+//!
+//! [features]
+//! parallel = ["dep:rayon"]
+//! ```
+//!
+//! and brings the parallel adapters in scope:
+//!
+//! ```ignore
+//! #[cfg(feature = "parallel")]
+//! use rayon::iter::ParallelIterator;
+//! ```
+
+/// Iterate over the items of a collection by reference, like `iter()`.
+#[macro_export]
+macro_rules! par_iter {
+    ($items:expr) => {{
+        #[cfg(feature = "parallel")]
+        let items = {
+            use ::rayon::iter::IntoParallelRefIterator;
+            $items.par_iter()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let items = $items.iter();
+        items
+    }};
+}
+
+/// Iterate over the lines of a string, like `lines()`.
+#[macro_export]
+macro_rules! par_lines {
+    ($text:expr) => {{
+        #[cfg(feature = "parallel")]
+        let lines = {
+            use ::rayon::str::ParallelString;
+            $text.par_lines()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let lines = $text.lines();
+        lines
+    }};
+}
@@ -0,0 +1,295 @@
+//! Examples of a day with their expected answers, listed in its
+//! `data/spec.txt` and checked by the tests of every day.
+//!
+//! Each example starts with a `=== name` line, followed by its expected
+//! answers and either the data file holding its input or the input itself,
+//! from a `---` line to the next example:
+//!
+//! ```text
+//! # Lines starting with # before the input are comments.
+//! === first example
+//! input: test_input.txt
+//! part 1: 13
+//! part 2: 30
+//!
+//! === small loop
+//! part 1: 4
+//! ---
+//! .....
+//! .S-7.
+//! .|.|.
+//! .L-J.
+//! .....
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, ensure, Context, Result};
+
+use crate::{diff, input, Answer, Day, Part, Solution};
+
+/// The spec of a day, in its data directory.
+pub const FILE: &str = "spec.txt";
+
+const HEADER: &str = "=== ";
+const SEPARATOR: &str = "---";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// A file, relative to the spec.
+    File(PathBuf),
+    Inline(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: Input,
+    pub expected: BTreeMap<Part, Answer>,
+}
+
+impl Example {
+    /// The input of the example, with files read from `dir`.
+    pub fn input(&self, dir: &Path) -> Result<String> {
+        match &self.input {
+            Input::File(path) => input::read(dir.join(path)),
+            Input::Inline(input) => Ok(input.clone()),
+        }
+    }
+}
+
+/// Parse the examples of a spec.
+pub fn parse(spec: &str) -> Result<Vec<Example>> {
+    let mut examples: Vec<Example> = Vec::new();
+    // The lines of the inline input being read, if any.
+    let mut inline: Option<Vec<&str>> = None;
+    let mut file = None;
+
+    fn finish(
+        examples: &mut [Example],
+        inline: Option<Vec<&str>>,
+        file: Option<PathBuf>,
+    ) -> Result<()> {
+        let Some(example) = examples.last_mut() else {
+            return Ok(());
+        };
+        example.input = match (inline, file) {
+            (Some(_), Some(_)) => bail!(
+                "{:?} has both an input file and an inline input",
+                example.name
+            ),
+            (Some(mut lines), None) => {
+                while lines.last().is_some_and(|line| line.trim().is_empty()) {
+                    lines.pop();
+                }
+                Input::Inline(lines.iter().map(|line| format!("{}\n", line)).collect())
+            }
+            (None, Some(path)) => Input::File(path),
+            (None, None) => bail!("{:?} has no input", example.name),
+        };
+        Ok(())
+    }
+
+    for (number, line) in spec.lines().enumerate() {
+        let context = || format!("line {}: {:?}", number + 1, line);
+        if let Some(name) = line.strip_prefix(HEADER) {
+            finish(&mut examples, inline.take(), file.take())?;
+            examples.push(Example {
+                name: name.trim().to_string(),
+                input: Input::Inline(String::new()),
+                expected: BTreeMap::new(),
+            });
+            continue;
+        }
+        if let Some(lines) = &mut inline {
+            lines.push(line);
+            continue;
+        }
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some(example) = examples.last_mut() else {
+            bail!("{}: expected a {:?} line first", context(), HEADER.trim());
+        };
+        if line == SEPARATOR {
+            inline = Some(Vec::new());
+        } else if let Some(path) = line.strip_prefix("input:") {
+            ensure!(file.is_none(), "{}: a second input file", context());
+            file = Some(PathBuf::from(path.trim()));
+        } else if let Some((part, answer)) = line
+            .strip_prefix("part ")
+            .and_then(|line| line.split_once(':'))
+        {
+            let part: Part = part
+                .trim()
+                .parse()
+                .map_err(anyhow::Error::msg)
+                .with_context(context)?;
+            let answer: Answer = answer
+                .trim()
+                .parse()
+                .map_err(anyhow::Error::msg)
+                .with_context(context)?;
+            ensure!(
+                example.expected.insert(part, answer).is_none(),
+                "{}: a second answer to part {}",
+                context(),
+                part
+            );
+        } else {
+            bail!(
+                "{}: expected `input: FILE`, `part N: ANSWER` or {:?}",
+                context(),
+                SEPARATOR
+            );
+        }
+    }
+    finish(&mut examples, inline, file)?;
+    Ok(examples)
+}
+
+/// The examples listed in `dir`'s spec.
+pub fn load(dir: &Path) -> Result<Vec<Example>> {
+    let path = dir.join(FILE);
+    parse(&input::read(&path)?).with_context(|| format!("invalid {}", path.display()))
+}
+
+/// How a day fares on an example part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub day: u8,
+    pub example: String,
+    pub part: Part,
+    pub expected: Answer,
+    /// The answer, or why there is none.
+    pub answer: Result<Answer, String>,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        self.answer.as_ref() == Ok(&self.expected)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02} {} part {}: ",
+            self.day, self.example, self.part
+        )?;
+        match &self.answer {
+            Ok(answer) if self.is_ok() => write!(f, "{} ok", answer),
+            Ok(answer) => write!(f, "{} FAILED, expected {}", answer, self.expected),
+            Err(error) => write!(f, "{} FAILED, expected {}", error, self.expected),
+        }
+    }
+}
+
+/// Solve each example listed in `dir`'s spec with `day`, panics included.
+pub fn check(day: &Day, dir: &Path) -> Result<Vec<Outcome>> {
+    let mut outcomes = Vec::new();
+    for example in load(dir)? {
+        let input = example.input(dir)?;
+        let model = diff::catch(|| day.parse(&input));
+        for (&part, expected) in &example.expected {
+            let answer = match &model {
+                Ok(Ok(model)) => diff::catch(|| day.solve(model, part))
+                    .map_err(|message| format!("panicked: {}", message))
+                    .and_then(|answer| answer.map_err(|error| format!("{:#}", error))),
+                Ok(Err(error)) => Err(format!("failed to parse: {:#}", error)),
+                Err(message) => Err(format!("panicked while parsing: {}", message)),
+            };
+            outcomes.push(Outcome {
+                day: day.day,
+                example: example.name.clone(),
+                part,
+                expected: expected.clone(),
+                answer,
+            });
+        }
+    }
+    Ok(outcomes)
+}
+
+/// Check the examples of `S` listed in `data/spec.txt`, for the tests of its
+/// crate; panics listing those that fail.
+pub fn assert_examples<S: Solution>()
+where
+    S::Model: 'static,
+{
+    let outcomes = check(&Day::new::<S>(), Path::new("data")).unwrap();
+    let failed: Vec<String> = outcomes
+        .iter()
+        .filter(|outcome| !outcome.is_ok())
+        .map(Outcome::to_string)
+        .collect();
+    assert!(
+        failed.is_empty(),
+        "{} of {} examples failed:\n{}",
+        failed.len(),
+        outcomes.len(),
+        failed.join("\n")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "\
+# Day 4
+=== first
+input: test_input.txt
+part 1: 13
+part 2: 30
+
+=== inline
+part 2: ABC
+---
+  indented
+# not a comment
+
+";
+
+    #[test]
+    fn test_parse() {
+        let examples = parse(SPEC).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "first");
+        assert_eq!(
+            examples[0].input,
+            Input::File(PathBuf::from("test_input.txt"))
+        );
+        assert_eq!(examples[0].expected[&Part::One], Answer::Int(13));
+        assert_eq!(examples[1].expected[&Part::Two], Answer::text("ABC"));
+        assert!(!examples[1].expected.contains_key(&Part::One));
+        assert_eq!(
+            examples[1].input,
+            Input::Inline("  indented\n# not a comment\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for spec in [
+            "part 1: 4",
+            "=== a\npart 1: 4",
+            "=== a\ninput: a.txt\ninput: b.txt",
+            "=== a\npart 3: 4\n---\nx",
+            "=== a\npart 1: 4\npart 1: 5\n---\nx",
+            "=== a\nanswer: 4\n---\nx",
+            "=== a\ninput: a.txt\n---\nx",
+        ] {
+            assert!(parse(spec).is_err(), "{:?}", spec);
+        }
+        let error = parse("=== a\npart 3: 4\n---\nx").unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            "line 2: \"part 3: 4\": invalid part \"3\", expected 1 or 2"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+//! Solving inputs read line by line, for days whose lines are independent,
+//! so that arbitrarily large inputs are solved in bounded memory.
+
+use std::io::BufRead;
+
+use anyhow::{Context, Result};
+
+use crate::{Answer, Part, Solution};
+
+/// A [`Solution`] that can solve its parts without holding its input.
+pub trait Stream: Solution {
+    /// Solve `part` from `input`, read once, with the same answer as
+    /// [`Solution::solve`] on the whole text.
+    fn solve_stream(input: &mut dyn BufRead, part: Part) -> Result<Answer>;
+}
+
+/// The lines of `input`, without their line endings.
+pub fn lines(input: &mut dyn BufRead) -> impl Iterator<Item = Result<String>> + '_ {
+    input
+        .lines()
+        .map(|line| line.context("failed to read a line"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let mut input = "a\r\nb\n\nc".as_bytes();
+        let read: Vec<String> = lines(&mut input).collect::<Result<_>>().unwrap();
+        assert_eq!(read, ["a", "b", "", "c"]);
+
+        let mut invalid: &[u8] = b"a\n\xff\n";
+        assert!(lines(&mut invalid).collect::<Result<Vec<_>>>().is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Structured events emitted by solvers, to see how they reach an answer.
+//!
+//! Solvers call [`emit`], which does nothing unless the calling thread runs
+//! under [`traced`]: events are then built and handed to its [`Sink`].
+//!
+//! ```
+//! use aoc::trace::{self, Event};
+//!
+//! let mut events = Vec::new();
+//! trace::traced(&mut |event: &Event| events.push(event.to_string()), || {
+//!     trace::emit(|| Event::new("step", "step 1 at (0,2)").field("step", 1));
+//! });
+//! assert_eq!(events, ["step: step 1 at (0,2)"]);
+//! ```
+
+use std::cell::Cell;
+use std::fmt;
+use std::ptr;
+
+/// The value of a field of an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Int(i64),
+    Text(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(n: $t) -> Self {
+                    Value::Int(n as i64)
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i8, i16, i32, i64, u8, u16, u32, usize);
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Value::Text(text.to_string())
+    }
+}
+
+/// Something a solver did, e.g. `card 3 adds 4 copies to cards 4–5`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// What kind of step this is, for filtering.
+    pub kind: &'static str,
+    pub message: String,
+    /// The values of the message, for tools.
+    pub fields: Vec<(&'static str, Value)>,
+}
+
+impl Event {
+    pub fn new(kind: &'static str, message: impl Into<String>) -> Self {
+        Event {
+            kind,
+            message: message.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn field(mut self, name: &'static str, value: impl Into<Value>) -> Self {
+        self.fields.push((name, value.into()));
+        self
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+/// Where events go.
+pub trait Sink {
+    fn event(&mut self, event: &Event);
+}
+
+impl<F: FnMut(&Event)> Sink for F {
+    fn event(&mut self, event: &Event) {
+        self(event)
+    }
+}
+
+type SinkPtr = *mut (dyn Sink + 'static);
+
+/// No sink; a null pointer with the vtable of some sink.
+const NONE: SinkPtr = ptr::null_mut::<fn(&Event)>() as SinkPtr;
+
+thread_local! {
+    static SINK: Cell<SinkPtr> = const { Cell::new(NONE) };
+}
+
+/// Restores the previous sink, even if `f` panics.
+struct Restore(SinkPtr);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        SINK.with(|sink| sink.set(self.0));
+    }
+}
+
+/// Run `f`, sending the events it emits on this thread to `sink`.
+pub fn traced<T>(sink: &mut dyn Sink, f: impl FnOnce() -> T) -> T {
+    // SAFETY: the lifetime is erased so that the sink fits the thread local;
+    // it is only used until `Restore` unsets it, before `sink` goes away.
+    let sink: SinkPtr = unsafe { std::mem::transmute(sink) };
+    let _restore = Restore(SINK.with(|current| current.replace(sink)));
+    f()
+}
+
+/// Whether events are being collected, to skip work only done for them.
+pub fn enabled() -> bool {
+    SINK.with(|sink| !sink.get().is_null())
+}
+
+/// Send the event built by `event` to the sink, if there is one.
+pub fn emit(event: impl FnOnce() -> Event) {
+    let sink = SINK.with(Cell::get);
+    if sink.is_null() {
+        return;
+    }
+    let event = event();
+    // Unset the sink meanwhile, so that a sink emitting events does not
+    // alias itself.
+    let _restore = Restore(SINK.with(|current| current.replace(NONE)));
+    // SAFETY: `traced` keeps the sink alive while it is set.
+    unsafe { (*sink).event(&event) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traced() {
+        emit(|| unreachable!("no sink"));
+        assert!(!enabled());
+
+        let mut outer = Vec::new();
+        let mut inner = Vec::new();
+        traced(&mut |e: &Event| outer.push(e.clone()), || {
+            emit(|| Event::new("a", "first").field("n", 1));
+            traced(&mut |e: &Event| inner.push(e.clone()), || {
+                assert!(enabled());
+                emit(|| Event::new("b", "nested"));
+            });
+            emit(|| Event::new("a", "second"));
+        });
+        assert!(!enabled());
+
+        assert_eq!(outer.len(), 2);
+        assert_eq!(outer[0].fields, [("n", Value::Int(1))]);
+        assert_eq!(outer[1].to_string(), "a: second");
+        assert_eq!(inner, [Event::new("b", "nested")]);
+    }
+
+    #[test]
+    fn test_panic_restores() {
+        let mut events = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            traced(&mut |_: &Event| events += 1, || panic!("oops"))
+        }));
+        assert!(result.is_err());
+        assert!(!enabled());
+    }
+}
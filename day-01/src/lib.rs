@@ -0,0 +1,172 @@
+use std::io::BufRead;
+
+use anyhow::{Context, Result};
+use aoc::stream::{self, Stream};
+use aoc::{par_iter, par_lines, Answer, Part, Unimplemented};
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct CalibrationDigits {
+    position: usize,
+    digit: u32,
+}
+
+impl CalibrationDigits {
+    fn new(position: usize, digit: u32) -> Self {
+        Self { position, digit }
+    }
+}
+
+impl PartialOrd for CalibrationDigits {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CalibrationDigits {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.position.cmp(&other.position)
+    }
+}
+
+const WORD_DIGIT: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn detect_word_digits(row: &str) -> Vec<CalibrationDigits> {
+    let mut result: Vec<CalibrationDigits> = Vec::new();
+    for (i, _) in row.char_indices() {
+        let substr = &row[i..];
+        for (digit, word) in (1..=9).zip(WORD_DIGIT) {
+            if substr.starts_with(word) {
+                result.push(CalibrationDigits::new(i, digit));
+            }
+        }
+    }
+    result
+}
+
+fn detect_digits(row: &str) -> Vec<CalibrationDigits> {
+    row.char_indices()
+        .filter(|(_, c)| c.is_ascii_digit())
+        .map(|(i, c)| CalibrationDigits::new(i, c.to_digit(10).unwrap()))
+        .collect()
+}
+
+fn parse_row(row: &str) -> Option<u32> {
+    let mut calibration_results: Vec<CalibrationDigits> = Vec::new();
+    calibration_results.extend(detect_digits(row));
+    calibration_results.extend(detect_word_digits(row));
+    calibration_results.sort();
+    let first_digit = calibration_results.first()?.digit;
+    let last_digit = calibration_results.last()?.digit;
+
+    let mut str_result = String::new();
+    str_result.push_str(first_digit.to_string().as_str());
+    str_result.push_str(last_digit.to_string().as_str());
+
+    str_result.parse().ok()
+}
+
+pub struct Day01;
+
+impl aoc::Solution for Day01 {
+    const DAY: u8 = 1;
+    type Model = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Model> {
+        Ok(par_lines!(input).map(str::to_string).collect())
+    }
+
+    fn part2(rows: &Self::Model) -> Result<Answer> {
+        let total_cal: u32 = par_iter!(rows)
+            .map(|row| parse_row(row))
+            .sum::<Option<u32>>()
+            .context("row without calibration digits")?;
+        Ok(total_cal.into())
+    }
+}
+
+impl Stream for Day01 {
+    fn solve_stream(input: &mut dyn BufRead, part: Part) -> Result<Answer> {
+        if part == Part::One {
+            return Err(Unimplemented.into());
+        }
+        let mut total_cal: u64 = 0;
+        for row in stream::lines(input) {
+            let cal = parse_row(&row?).context("row without calibration digits")?;
+            total_cal += u64::from(cal);
+        }
+        Ok(total_cal.into())
+    }
+}
+
+impl aoc::fuzz::Fuzz for Day01 {
+    fn exercise(rows: &Self::Model) {
+        for row in rows {
+            parse_row(row);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_row() {
+        let results = [
+            ("1abc2", Some(12)),
+            ("pqr3stu8vwx", Some(38)),
+            ("a1b2c3d4e5f", Some(15)),
+            ("treb7uchet", Some(77)),
+            ("1oneninegspfm3four43", Some(13)),
+        ];
+        for (row, expected) in results {
+            assert_eq!(parse_row(row), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_row_word_digits() {
+        let results = [
+            ("two1nine", Some(29)),
+            ("eightwothree", Some(83)),
+            ("abcone2threexyz", Some(13)),
+            ("xtwone3four", Some(24)),
+            ("4nineeightseven2", Some(42)),
+            ("zoneight234", Some(14)),
+            ("7pqrstsixteen", Some(76)),
+            ("1oneninegspfm3four43one", Some(11)),
+            ("1oneninegspfm3four43eightwo", Some(12)),
+        ];
+        for (row, expected) in results {
+            assert_eq!(parse_row(row), expected);
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        aoc::spec::assert_examples::<Day01>();
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        aoc::fuzz::assert_regressions::<Day01>();
+    }
+
+    #[test]
+    fn test_stream() {
+        use aoc::Solution;
+
+        let input = "two1nine\neightwothree\nabcone2threexyz\n";
+        let rows = Day01::parse(input).unwrap();
+        assert_eq!(
+            Day01::solve_stream(&mut input.as_bytes(), Part::Two).unwrap(),
+            Day01::part2(&rows).unwrap()
+        );
+        assert!(Day01::solve_stream(&mut input.as_bytes(), Part::One).is_err());
+        assert!(Day01::solve_stream(&mut "abc\n".as_bytes(), Part::Two).is_err());
+    }
+}
@@ -0,0 +1,353 @@
+use std::io::BufRead;
+
+use anyhow::{Context, Error, Result};
+use aoc::anonymize::{Anonymize, Anonymized};
+use aoc::diff::Rng;
+use aoc::stream::{self, Stream};
+use aoc::{par_iter, par_lines, Answer, Part};
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+use regex::Regex;
+
+/// The bag of part 1.
+const MAX_CUBES: ColorRecord = ColorRecord {
+    red: Some(12),
+    green: Some(13),
+    blue: Some(14),
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct ColorRecord {
+    red: Option<u32>,
+    green: Option<u32>,
+    blue: Option<u32>,
+}
+
+impl ColorRecord {
+    fn new(red: Option<u32>, green: Option<u32>, blue: Option<u32>) -> Self {
+        Self { red, green, blue }
+    }
+
+    fn parse(input: &str) -> Result<ColorRecord> {
+        let red_pattern = Regex::new(r"(?<count>\d+) red").unwrap();
+        let green_pattern = Regex::new(r"(?<count>\d+) green").unwrap();
+        let blue_pattern = Regex::new(r"(?<count>\d+) blue").unwrap();
+        let get_count = |pattern: Regex| -> Result<Option<u32>> {
+            let Some(c) = pattern.captures(input) else {
+                return Ok(None);
+            };
+            let count = c["count"]
+                .parse()
+                .with_context(|| format!("invalid count {:?}", &c["count"]))?;
+            Ok(Some(count))
+        };
+        Ok(ColorRecord::new(
+            get_count(red_pattern)?,
+            get_count(green_pattern)?,
+            get_count(blue_pattern)?,
+        ))
+    }
+
+    fn possible(&self, max_cubes: &ColorRecord) -> bool {
+        (self.red <= max_cubes.red)
+            && (self.green <= max_cubes.green)
+            && (self.blue <= max_cubes.blue)
+    }
+
+    /// The counts of the record, such as `3 blue`.
+    fn counts(&self) -> Vec<String> {
+        [
+            (self.red, "red"),
+            (self.green, "green"),
+            (self.blue, "blue"),
+        ]
+        .into_iter()
+        .filter_map(|(count, color)| Some(format!("{} {}", count?, color)))
+        .collect()
+    }
+
+    fn power(&self) -> Result<u32> {
+        self.red
+            .unwrap_or_default()
+            .checked_mul(self.green.unwrap_or_default())
+            .and_then(|power| power.checked_mul(self.blue.unwrap_or_default()))
+            .context("power overflows")
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct GameRecord {
+    id: u32,
+    records: Vec<ColorRecord>,
+}
+
+impl GameRecord {
+    fn new(id: u32, records: Vec<ColorRecord>) -> Self {
+        Self { id, records }
+    }
+
+    /// The game of line `input`, if it is one.
+    fn parse(input: &str) -> Result<Option<GameRecord>> {
+        let row_pattern = Regex::new(r"^Game (?<ID>\d+): (?<records>.*)$").unwrap();
+        let Some(caps) = row_pattern.captures(input) else {
+            return Ok(None);
+        };
+
+        let record = GameRecord::new(
+            caps["ID"]
+                .parse()
+                .with_context(|| format!("invalid game id {:?}", &caps["ID"]))?,
+            caps["records"]
+                .split(';')
+                .map(ColorRecord::parse)
+                .collect::<Result<_>>()?,
+        );
+        Ok(Some(record))
+    }
+
+    fn max(&self) -> ColorRecord {
+        self.records
+            .iter()
+            .fold(ColorRecord::new(None, None, None), |acc, x| {
+                ColorRecord::new(
+                    acc.red.max(x.red),
+                    acc.green.max(x.green),
+                    acc.blue.max(x.blue),
+                )
+            })
+    }
+    fn max_power(&self) -> Result<u32> {
+        self.max().power()
+    }
+}
+
+impl Iterator for GameRecord {
+    type Item = ColorRecord;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.records.pop()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GameRecords(Vec<GameRecord>);
+
+impl GameRecords {
+    fn new(records: Vec<GameRecord>) -> Self {
+        Self(records)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &GameRecord> {
+        self.0.iter()
+    }
+}
+
+impl TryFrom<&str> for GameRecords {
+    type Error = Error;
+
+    fn try_from(input: &str) -> Result<Self> {
+        let records = par_lines!(input)
+            .filter_map(|line| GameRecord::parse(line).transpose())
+            .collect::<Result<_>>()?;
+        Ok(GameRecords::new(records))
+    }
+}
+
+impl TryFrom<String> for GameRecords {
+    type Error = Error;
+
+    fn try_from(input: String) -> Result<Self> {
+        GameRecords::try_from(input.as_str())
+    }
+}
+
+impl From<Vec<GameRecord>> for GameRecords {
+    fn from(records: Vec<GameRecord>) -> Self {
+        GameRecords::new(records)
+    }
+}
+
+fn get_possible_games(game_records: &GameRecords, max_cubes: ColorRecord) -> Vec<u32> {
+    let max_by_game: Vec<(u32, ColorRecord)> =
+        par_iter!(game_records.0).map(|x| (x.id, x.max())).collect();
+    let possible_games: Vec<(u32, ColorRecord)> = max_by_game
+        .into_iter()
+        .filter(|x| x.1.possible(&max_cubes))
+        .collect();
+    possible_games.iter().map(|x| x.0).collect()
+}
+
+pub struct Day02;
+
+impl aoc::Solution for Day02 {
+    const DAY: u8 = 2;
+    type Model = GameRecords;
+
+    fn parse(input: &str) -> Result<Self::Model> {
+        input.try_into()
+    }
+
+    fn part1(game_records: &Self::Model) -> Result<Answer> {
+        let possible_ids = get_possible_games(game_records, MAX_CUBES);
+        let sum = possible_ids
+            .iter()
+            .try_fold(0_u32, |sum, &id| sum.checked_add(id))
+            .context("sum of ids overflows")?;
+        Ok(sum.into())
+    }
+
+    fn part2(game_records: &Self::Model) -> Result<Answer> {
+        let powers: Vec<u32> = par_iter!(game_records.0)
+            .map(|x| x.max_power())
+            .collect::<Result<_>>()?;
+        let max_power = powers
+            .into_iter()
+            .try_fold(0_u32, |sum, power| sum.checked_add(power))
+            .context("sum of powers overflows")?;
+        Ok(max_power.into())
+    }
+}
+
+impl aoc::fuzz::Fuzz for Day02 {
+    fn exercise(game_records: &Self::Model) {
+        get_possible_games(game_records, MAX_CUBES);
+        for game_record in game_records.iter() {
+            let _ = game_record.max_power();
+        }
+    }
+}
+
+impl Stream for Day02 {
+    fn solve_stream(input: &mut dyn BufRead, part: Part) -> Result<Answer> {
+        let mut total: u64 = 0;
+        for line in stream::lines(input) {
+            let Some(game_record) = GameRecord::parse(&line?)? else {
+                continue;
+            };
+            total += u64::from(match part {
+                Part::One if GameRecord::max(&game_record).possible(&MAX_CUBES) => game_record.id,
+                Part::One => 0,
+                Part::Two => game_record.max_power()?,
+            });
+        }
+        Ok(total.into())
+    }
+}
+
+/// Ids are permuted among the possible games and among the others, which
+/// keeps the sum of part 1. Games, their reveals and the colors of each reveal
+/// are shuffled as well.
+impl Anonymize for Day02 {
+    fn anonymize(input: &str, rng: &mut Rng) -> Result<Anonymized> {
+        let games = GameRecords::try_from(input)?;
+        let possible = |game: &GameRecord| game.max().possible(&MAX_CUBES);
+        let mut ids: [Vec<u32>; 2] = [false, true].map(|wanted| {
+            let mut ids: Vec<u32> = games
+                .iter()
+                .filter(|game| possible(game) == wanted)
+                .map(|game| game.id)
+                .collect();
+            rng.shuffle(&mut ids);
+            ids
+        });
+        let mut lines: Vec<String> = games
+            .iter()
+            .map(|game| {
+                let id = ids[possible(game) as usize].pop().unwrap();
+                let mut reveals: Vec<String> = game
+                    .records
+                    .iter()
+                    .map(|record| {
+                        let mut counts = record.counts();
+                        rng.shuffle(&mut counts);
+                        counts.join(", ")
+                    })
+                    .collect();
+                rng.shuffle(&mut reveals);
+                format!("Game {}: {}", id, reveals.join("; "))
+            })
+            .collect();
+        rng.shuffle(&mut lines);
+        Ok(Anonymized::same(lines.join("\n") + "\n"))
+    }
+}
+
+#[test]
+fn test_parse_row() {
+    let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+    let expected = GameRecord::new(
+        1,
+        vec![
+            ColorRecord::new(Some(4), None, Some(3)),
+            ColorRecord::new(Some(1), Some(2), Some(6)),
+            ColorRecord::new(None, Some(2), None),
+        ],
+    );
+    assert_eq!(GameRecord::parse(input).unwrap(), Some(expected));
+}
+
+#[test]
+fn test_fake_record() {
+    let game_records: GameRecords = aoc::input::read("./data/test_record.txt")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let possible_ids = get_possible_games(&game_records, MAX_CUBES);
+    let total_possible = possible_ids.iter().sum::<u32>();
+    assert_eq!(total_possible, 8);
+}
+
+#[test]
+fn test_fake_record_power() {
+    let game_records: GameRecords = aoc::input::read("./data/test_record.txt")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let max_power = game_records
+        .iter()
+        .map(|x| x.max_power().unwrap())
+        .sum::<u32>();
+    assert_eq!(max_power, 2286);
+}
+
+#[test]
+fn test_anonymize() {
+    use aoc::Solution;
+
+    let input = aoc::input::read("./data/test_record.txt").unwrap();
+    let original = Day02::parse(&input).unwrap();
+    for seed in 0..5 {
+        let anonymized = Day02::anonymize(&input, &mut Rng::new(seed)).unwrap();
+        assert_eq!(anonymized.shift, 0);
+        let game_records = Day02::parse(&anonymized.input).unwrap();
+        assert_eq!(game_records.0.len(), original.0.len());
+        assert_eq!(Day02::part1(&game_records).unwrap(), Answer::Int(8));
+        assert_eq!(Day02::part2(&game_records).unwrap(), Answer::Int(2286));
+    }
+}
+
+#[test]
+fn test_examples() {
+    aoc::spec::assert_examples::<Day02>();
+}
+
+#[test]
+fn test_fuzz_regressions() {
+    aoc::fuzz::assert_regressions::<Day02>();
+}
+
+#[test]
+fn test_stream() {
+    let mut input = aoc::input::open("./data/test_record.txt").unwrap();
+    assert_eq!(
+        Day02::solve_stream(&mut input, Part::One).unwrap(),
+        Answer::Int(8)
+    );
+    let mut input = aoc::input::open("./data/test_record.txt").unwrap();
+    assert_eq!(
+        Day02::solve_stream(&mut input, Part::Two).unwrap(),
+        Answer::Int(2286)
+    );
+}
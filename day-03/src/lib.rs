@@ -0,0 +1,186 @@
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use aoc::Answer;
+use regex::Regex;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct PartNumber {
+    number: u32,
+    col_start: u32,
+    col_end: u32,
+    row: u32,
+}
+
+impl PartNumber {
+    fn new(number: u32, col: u32, row: u32) -> Self {
+        let ndigits = number.to_string().len();
+        Self {
+            number,
+            col_start: col,
+            col_end: col + ndigits as u32 - 1,
+            row,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Symbol {
+    symb: char,
+    row: u32,
+    col: u32,
+}
+
+impl Symbol {
+    fn new(symb: char, row: u32, col: u32) -> Self {
+        Self { symb, row, col }
+    }
+
+    fn find_adjacent(self, parts: &[PartNumber]) -> Vec<PartNumber> {
+        parts
+            .iter()
+            .filter(|p| p.row + 1 >= self.row && p.row <= self.row + 1)
+            .filter(|p| p.col_start <= self.col + 1 && p.col_end + 1 >= self.col)
+            .copied()
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Schematic {
+    parts: Vec<PartNumber>,
+    symbols: Vec<Symbol>,
+}
+
+impl Schematic {
+    fn new(parts: Vec<PartNumber>, symbols: Vec<Symbol>) -> Self {
+        Self { parts, symbols }
+    }
+
+    fn add_part(&mut self, part: PartNumber) {
+        self.parts.push(part);
+    }
+
+    fn add_symbol(&mut self, symbol: Symbol) {
+        self.symbols.push(symbol);
+    }
+
+    fn parse(input: &str) -> Result<Self> {
+        let number_re = Regex::new(r"[0-9]+").unwrap();
+        let mut schematic = Self::default();
+
+        for (row, line) in input.lines().enumerate() {
+            for m in number_re.find_iter(line) {
+                let number = m
+                    .as_str()
+                    .parse()
+                    .with_context(|| format!("invalid part number {:?}", m.as_str()))?;
+                schematic.add_part(PartNumber::new(number, m.start() as u32, row as u32));
+            }
+
+            for (col, symbol) in line.chars().enumerate() {
+                if !symbol.is_ascii_digit() && symbol != '.' {
+                    schematic.add_symbol(Symbol::new(symbol, row as u32, col as u32));
+                }
+            }
+        }
+
+        Ok(schematic)
+    }
+
+    fn get_valid_parts(&self) -> Vec<PartNumber> {
+        self.symbols
+            .iter()
+            .flat_map(|s| s.find_adjacent(&self.parts))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn get_gears(&self) -> Vec<u64> {
+        self.symbols
+            .iter()
+            .filter(|x| x.symb == '*')
+            .map(|x| x.find_adjacent(&self.parts))
+            .filter(|x| x.len() == 2)
+            .map(|x| u64::from(x[0].number) * u64::from(x[1].number))
+            .collect()
+    }
+}
+
+pub struct Day03;
+
+impl aoc::Solution for Day03 {
+    const DAY: u8 = 3;
+    type Model = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Model> {
+        Schematic::parse(input)
+    }
+
+    fn part1(schematic: &Self::Model) -> Result<Answer> {
+        let total = schematic
+            .get_valid_parts()
+            .iter()
+            .map(|x| u64::from(x.number))
+            .sum::<u64>();
+        Ok(total.into())
+    }
+
+    fn part2(schematic: &Self::Model) -> Result<Answer> {
+        let gears = schematic.get_gears();
+        let total = gears
+            .iter()
+            .try_fold(0_u64, |acc, &x| acc.checked_add(x))
+            .context("sum of gear ratios overflows")?;
+        Ok(total.into())
+    }
+}
+
+impl aoc::fuzz::Fuzz for Day03 {
+    fn exercise(schematic: &Self::Model) {
+        schematic.get_valid_parts();
+        schematic.get_gears();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = aoc::input::read("data/test_input.txt").unwrap();
+        let schematic = Schematic::parse(&content).unwrap();
+        println!("{:#?}", schematic);
+        assert_eq!(schematic.parts.len(), 10);
+        assert_eq!(schematic.symbols.len(), 6);
+    }
+
+    #[test]
+    fn test_part01() {
+        let content = aoc::input::read("data/test_input.txt").unwrap();
+        let schematic = Schematic::parse(&content).unwrap();
+        let total = schematic
+            .get_valid_parts()
+            .iter()
+            .map(|x| x.number)
+            .sum::<u32>();
+        assert_eq!(total, 4361);
+    }
+
+    #[test]
+    fn test_examples() {
+        aoc::spec::assert_examples::<Day03>();
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        aoc::fuzz::assert_regressions::<Day03>();
+    }
+}
@@ -0,0 +1,341 @@
+use anyhow::{ensure, Context, Result};
+use aoc::num::{Int, Numeric};
+use aoc::stream::{self, Stream};
+use aoc::trace::{self, Event, Value};
+use aoc::{par_iter, par_lines, Answer, Part};
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+use regex::Regex;
+use std::collections::{HashSet, VecDeque};
+use std::{cmp::Reverse, io::BufRead};
+
+/// The score of a card with `n_win` winning numbers, unless it overflows.
+fn score<N: Int>(n_win: u32) -> Option<N> {
+    if n_win < 1 {
+        return Some(N::zero());
+    }
+    (1..n_win).try_fold(N::one(), |score, _| score.try_add(&score))
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Scratchcard {
+    id: Reverse<u32>,
+    win_num: HashSet<u32>,
+    my_num: Vec<u32>,
+}
+
+impl Scratchcard {
+    fn new(id: u32, win_num: Vec<u32>, my_num: Vec<u32>) -> Scratchcard {
+        Scratchcard {
+            id: Reverse(id),
+            win_num: win_num.into_iter().collect(),
+            my_num,
+        }
+    }
+
+    fn id(&self) -> u32 {
+        self.id.0
+    }
+
+    fn nums_winning(&self) -> Vec<u32> {
+        self.my_num
+            .iter()
+            .filter(|n| self.win_num.contains(n))
+            .copied()
+            .collect()
+    }
+
+    fn n_winning(&self) -> u32 {
+        self.nums_winning().len() as u32
+    }
+
+    fn score<N: Int>(&self) -> Result<N> {
+        score(self.n_winning())
+            .with_context(|| format!("score of card {} overflows {}", self.id(), N::NAME))
+    }
+
+    /// The card of line `s`, if it is one.
+    fn parse(s: &str) -> Result<Option<Scratchcard>> {
+        let pattern = Regex::new(r"^Card +(\d+): (.*) \| (.*)$").expect("Regex invalid");
+        let Some(cap) = pattern.captures(s) else {
+            return Ok(None);
+        };
+        let id: u32 = cap[1]
+            .parse()
+            .with_context(|| format!("invalid card id {:?}", &cap[1]))?;
+        let numbers = |s: &str| -> Result<Vec<u32>> {
+            s.split_whitespace()
+                .map(|n| n.parse().with_context(|| format!("invalid number {:?}", n)))
+                .collect()
+        };
+        Ok(Some(Scratchcard::new(
+            id,
+            numbers(&cap[2])?,
+            numbers(&cap[3])?,
+        )))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Pile {
+    cards: Vec<Scratchcard>,
+}
+
+impl Pile {
+    fn new(cards: Vec<Scratchcard>) -> Pile {
+        Pile { cards }
+    }
+
+    /// Cards must be numbered from 1 in order, as they win copies of the
+    /// cards after them by number.
+    fn parse(s: &str) -> Result<Pile> {
+        let cards: Vec<Scratchcard> = par_lines!(s)
+            .filter_map(|line| Scratchcard::parse(line).transpose())
+            .collect::<Result<_>>()?;
+        for (i, card) in cards.iter().enumerate() {
+            ensure!(
+                card.id() as usize == i + 1,
+                "card {} should be card {}",
+                card.id(),
+                i + 1
+            );
+        }
+        Ok(Pile::new(cards))
+    }
+
+    fn scratchcards(&self) -> &[Scratchcard] {
+        &self.cards
+    }
+
+    fn scores<N: Int>(&self) -> Result<Vec<N>> {
+        par_iter!(self.scratchcards()).map(|c| c.score()).collect()
+    }
+
+    /// Add the copies of `card` won to the `duplicates` of the cards after it.
+    fn duplicate_card<N: Int>(duplicates: &mut [N], card: &Scratchcard) {
+        let id = card.id();
+        let n_winning = card.n_winning();
+        let curr_duplicate = duplicates[id as usize - 1].clone();
+        if n_winning > 0 {
+            trace::emit(|| {
+                let last = (id + n_winning).min(duplicates.len() as u32);
+                let copies = match curr_duplicate.to_i64() {
+                    Some(copies) => Value::Int(copies),
+                    None => Value::Text(curr_duplicate.to_string()),
+                };
+                Event::new(
+                    "copies",
+                    format!(
+                        "card {} adds {} copies to cards {}–{}",
+                        id,
+                        curr_duplicate,
+                        id + 1,
+                        last
+                    ),
+                )
+                .field("card", id)
+                .field("copies", copies)
+                .field("from", id + 1)
+                .field("to", last)
+            });
+        }
+        for incr in 1..=n_winning {
+            let i = (id + incr) as usize - 1;
+            if i >= duplicates.len() {
+                break;
+            }
+            duplicates[i] = duplicates[i].clone() + curr_duplicate.clone();
+        }
+    }
+}
+
+fn part01<N: Int>(pile: &Pile) -> Result<N> {
+    pile.scores()?
+        .into_iter()
+        .try_fold(N::zero(), |total, score: N| total.try_add(&score))
+        .with_context(|| format!("total score overflows {}", N::NAME))
+}
+
+fn part02<N: Int>(pile: &Pile) -> N {
+    let mut duplicates = vec![N::one(); pile.cards.len()];
+    pile.cards
+        .iter()
+        .for_each(|c| Pile::duplicate_card(&mut duplicates, c));
+    duplicates.into_iter().sum()
+}
+
+pub struct Day04;
+
+impl aoc::Solution for Day04 {
+    const DAY: u8 = 4;
+    type Model = Pile;
+
+    fn parse(input: &str) -> Result<Self::Model> {
+        Pile::parse(input)
+    }
+
+    fn part1(pile: &Self::Model) -> Result<Answer> {
+        Ok(part01::<i64>(pile)?.into())
+    }
+
+    fn part2(pile: &Self::Model) -> Result<Answer> {
+        Ok(part02::<i64>(pile).into())
+    }
+}
+
+impl Numeric for Day04 {
+    fn solve_with<N: Int>(input: &str, part: Part) -> Result<Answer> {
+        let pile = Pile::parse(input)?;
+        match part {
+            Part::One => Ok(part01::<N>(&pile)?.into_answer()),
+            Part::Two => Ok(part02::<N>(&pile).into_answer()),
+        }
+    }
+}
+
+/// Cards are taken in order, each winning copies of the next ones, so only
+/// the copies won for as many cards as the most winning numbers are kept.
+/// Numbers are those of the in-memory parts, so that both modes agree.
+impl Stream for Day04 {
+    fn solve_stream(input: &mut dyn BufRead, part: Part) -> Result<Answer> {
+        Ok(stream_with::<i64>(input, part)?.into_answer())
+    }
+}
+
+fn stream_with<N: Int>(input: &mut dyn BufRead, part: Part) -> Result<N> {
+    let mut won: VecDeque<N> = VecDeque::new();
+    let mut total = N::zero();
+    let mut n_cards = 0;
+    for line in stream::lines(input) {
+        let Some(card) = Scratchcard::parse(&line?)? else {
+            continue;
+        };
+        n_cards += 1;
+        ensure!(
+            card.id() == n_cards,
+            "card {} should be card {}",
+            card.id(),
+            n_cards
+        );
+        total = match part {
+            Part::One => total
+                .try_add(&card.score()?)
+                .with_context(|| format!("total score overflows {}", N::NAME))?,
+            Part::Two => {
+                let copies = N::one() + won.pop_front().unwrap_or_else(N::zero);
+                let n_winning = card.n_winning() as usize;
+                if won.len() < n_winning {
+                    won.resize(n_winning, N::zero());
+                }
+                won.iter_mut()
+                    .take(n_winning)
+                    .for_each(|n| *n = n.clone() + copies.clone());
+                total + copies
+            }
+        };
+    }
+    Ok(total)
+}
+
+impl aoc::fuzz::Fuzz for Day04 {
+    fn exercise(pile: &Self::Model) {
+        let _ = part01::<i64>(pile);
+        part02::<i64>(pile);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_p1() {
+        let content = aoc::input::read("data/test_input.txt").unwrap();
+        let pile = Pile::parse(&content).unwrap();
+        assert_eq!(13, part01::<i64>(&pile).unwrap());
+    }
+
+    #[test]
+    fn test_p2() {
+        let content = aoc::input::read("data/test_input.txt").unwrap();
+        let pile = Pile::parse(&content).unwrap();
+        assert_eq!(30, part02::<i64>(&pile));
+    }
+
+    #[test]
+    fn test_trace() {
+        let content = aoc::input::read("data/test_input.txt").unwrap();
+        let pile = Pile::parse(&content).unwrap();
+        let mut events = Vec::new();
+        trace::traced(&mut |e: &Event| events.push(e.to_string()), || {
+            part02::<i64>(&pile)
+        });
+        assert_eq!(events[0], "copies: card 1 adds 1 copies to cards 2–5");
+        assert_eq!(events[1], "copies: card 2 adds 2 copies to cards 3–4");
+        assert_eq!(events.len(), 4);
+    }
+
+    #[test]
+    fn test_examples() {
+        aoc::spec::assert_examples::<Day04>();
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        aoc::fuzz::assert_regressions::<Day04>();
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut input = aoc::input::open("data/test_input.txt").unwrap();
+        assert_eq!(
+            Day04::solve_stream(&mut input, Part::One).unwrap(),
+            Answer::Int(13)
+        );
+        let mut input = aoc::input::open("data/test_input.txt").unwrap();
+        assert_eq!(
+            Day04::solve_stream(&mut input, Part::Two).unwrap(),
+            Answer::Int(30)
+        );
+
+        // Scores past `u32` are summed alike in both modes.
+        let numbers = (1..=40)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let cards = format!("Card 1: {0} | {0}\nCard 2: {0} | {0}\n", numbers);
+        let pile = Pile::parse(&cards).unwrap();
+        let streamed = Day04::solve_stream(&mut cards.as_bytes(), Part::One).unwrap();
+        assert_eq!(streamed, Answer::Int(1 << 40));
+        assert_eq!(streamed, <Day04 as aoc::Solution>::part1(&pile).unwrap());
+    }
+
+    #[test]
+    fn test_numbers() {
+        use aoc::num::{BigInt, Checked};
+
+        let content = aoc::input::read("data/test_input.txt").unwrap();
+        let pile = Pile::parse(&content).unwrap();
+        assert_eq!(part02::<i128>(&pile), 30);
+        assert_eq!(part02::<BigInt>(&pile), BigInt::from(30));
+        assert_eq!(part02::<Checked<i64>>(&pile), Checked(30));
+        assert_eq!(
+            Day04::solve_with::<BigInt>(&content, Part::Two).unwrap(),
+            Answer::Int(30)
+        );
+
+        let numbers = (1..=64)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let card = format!("Card 1: {0} | {0}", numbers);
+        assert!(Day04::solve_with::<i64>(&card, Part::One).is_err());
+        assert_eq!(
+            Day04::solve_with::<i128>(&card, Part::One).unwrap(),
+            Answer::from(1_i128 << 63)
+        );
+    }
+}
@@ -0,0 +1,816 @@
+#![allow(dead_code)]
+use anyhow::{bail, ensure, Context, Result};
+use aoc::anonymize::{Anonymize, Anonymized};
+use aoc::diff::{self, Differential, Implementation, Rng};
+use aoc::explore::{self, Command, Explore};
+use aoc::num::{Int, Numeric};
+use aoc::{par_iter, Answer, Part};
+use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+use std::collections::HashSet;
+use std::ops::Range;
+
+use regex::Regex;
+
+const CHUNK_SIZE: usize = 1_000_000;
+
+fn build_location_regex(from: &str, to: &str) -> Regex {
+    Regex::new(format!(r"(?m){}-to-{} map:\n(?<map>(\d+\s?)+)", from, to).as_str()).unwrap()
+}
+
+fn parse_seeds<N: Int>(input: &str) -> Result<Vec<N>> {
+    let seed_regex = Regex::new(r"seeds: (?<seeds>(\d+\s)+)").unwrap();
+    let c = seed_regex
+        .captures(input)
+        .context("failed to parse seeds")?;
+    c["seeds"].split_whitespace().map(N::parse).collect()
+}
+
+const SEED_STEPS: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct LocationRange<N> {
+    dst_start: N,
+    src_start: N,
+    length: N,
+}
+
+impl<N: Int> LocationRange<N> {
+    fn new(dst_start: N, src_start: N, length: N) -> Self {
+        LocationRange {
+            dst_start,
+            src_start,
+            length,
+        }
+    }
+
+    fn parse(row: &str) -> Result<Self> {
+        let numbers: Vec<N> = row
+            .split_whitespace()
+            .map(N::parse)
+            .collect::<Result<_>>()?;
+        let Ok([dst_start, src_start, length]) = <[N; 3]>::try_from(numbers) else {
+            bail!("invalid range {:?}", row);
+        };
+        ensure!(
+            src_start.try_add(&length).is_some() && dst_start.try_add(&length).is_some(),
+            "range {:?} overflows {}",
+            row,
+            N::NAME
+        );
+        Ok(LocationRange::new(dst_start, src_start, length))
+    }
+
+    fn src_range(&self) -> Range<N> {
+        self.src_start.clone()..(self.src_start.clone() + self.length.clone())
+    }
+
+    fn dst_range(&self) -> Range<N> {
+        self.dst_start.clone()..(self.dst_start.clone() + self.length.clone())
+    }
+
+    fn map(&self, src: &N) -> Option<N> {
+        if !self.src_range().contains(src) {
+            return None;
+        }
+        let offset = src.clone() - self.src_start.clone();
+        Some(self.dst_start.clone() + offset)
+    }
+
+    fn reverse_map(&self, dst: &N) -> Option<N> {
+        if !self.dst_range().contains(dst) {
+            return None;
+        }
+        let offset = dst.clone() - self.dst_start.clone();
+        Some(self.src_start.clone() + offset)
+    }
+    fn split_range(&self, src_range: Range<N>) -> (Vec<Range<N>>, Option<Range<N>>) {
+        let loc_src_range = self.src_range();
+        let left = src_range.start.clone().max(loc_src_range.start);
+        let right = src_range.end.clone().min(loc_src_range.end);
+        if left >= right {
+            return (vec![src_range], None);
+        }
+        let src_ranges = [src_range.start..left.clone(), right.clone()..src_range.end]
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .collect();
+        let offset = self.dst_start.clone() - self.src_start.clone();
+        (src_ranges, Some((left + offset.clone())..(right + offset)))
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Location<N> {
+    from: String,
+    to: String,
+    ranges: HashSet<LocationRange<N>>,
+}
+
+impl<N: Int> Location<N> {
+    fn new(from: &str, to: &str) -> Self {
+        Location {
+            from: from.to_string(),
+            to: to.to_string(),
+            ranges: HashSet::new(),
+        }
+    }
+
+    fn map(&self, input: N) -> N {
+        for range in &self.ranges {
+            if let Some(mapped) = range.map(&input) {
+                return mapped;
+            }
+        }
+        input
+    }
+
+    fn reverse_map(&self, input: N) -> N {
+        for range in &self.ranges {
+            if let Some(mapped) = range.reverse_map(&input) {
+                return mapped;
+            }
+        }
+        input
+    }
+
+    fn parse_ranges(&mut self, input: &str) -> Result<()> {
+        let location_regex = build_location_regex(&self.from, &self.to);
+        let c = location_regex
+            .captures(input)
+            .with_context(|| format!("failed to parse ranges ({}-to-{})", self.from, self.to))?;
+        let map = &c["map"];
+        let ranges = map
+            .lines()
+            .map(LocationRange::parse)
+            .collect::<Result<HashSet<_>>>()?;
+        self.ranges.extend(ranges);
+        Ok(())
+    }
+
+    fn map_ranges(&self, ranges: Vec<Range<N>>) -> Vec<Range<N>> {
+        let mut dst_ranges = Vec::new();
+        let mut unseen = ranges.clone();
+
+        for loc_range in &self.ranges {
+            let mut still_here = Vec::new();
+            while let Some(range) = unseen.pop() {
+                let (src_ranges, dst_range) = loc_range.split_range(range);
+
+                if let Some(dst_range) = dst_range {
+                    dst_ranges.push(dst_range);
+                }
+                still_here.extend(src_ranges)
+            }
+
+            unseen.extend(still_here);
+        }
+
+        dst_ranges.into_iter().chain(unseen).collect()
+    }
+}
+
+/// The almanach, with numbers of type `N`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Almanach<N = i64> {
+    seeds: Vec<N>,
+    locations: Vec<Location<N>>,
+}
+
+impl<N: Int> Almanach<N> {
+    fn new(seeds: Vec<N>, locations: Vec<Location<N>>) -> Self {
+        Almanach { seeds, locations }
+    }
+
+    fn parse(input: &str) -> Result<Self> {
+        let steps = SEED_STEPS
+            .into_iter()
+            .zip(SEED_STEPS.into_iter().skip(1))
+            .collect::<Vec<_>>();
+        let locations: Vec<Location<N>> = steps
+            .into_iter()
+            .map(|(from, to)| {
+                let mut location = Location::new(from, to);
+                location.parse_ranges(input)?;
+                Ok(location)
+            })
+            .collect::<Result<_>>()?;
+        let seeds = parse_seeds(input)?;
+        Ok(Almanach::new(seeds, locations))
+    }
+
+    /// The location a seed ends up at.
+    pub fn get_dst(&self, src: &N) -> N {
+        self.locations
+            .iter()
+            .fold(src.clone(), |src, location| location.map(src))
+    }
+
+    /// The seed a location comes from, whether or not it was planted.
+    pub fn get_src(&self, dst: &N) -> N {
+        self.locations
+            .iter()
+            .rev()
+            .fold(dst.clone(), |dst, location| location.reverse_map(dst))
+    }
+
+    /// The seeds of part 2, read as pairs of a start and a length.
+    fn seed_intervals(&self) -> Result<Vec<Range<N>>> {
+        self.seeds
+            .iter()
+            .tuples()
+            .map(|(start, length)| {
+                let end = start.try_add(length).with_context(|| {
+                    format!("seed range {} {} overflows {}", start, length, N::NAME)
+                })?;
+                Ok(start.clone()..end)
+            })
+            .collect()
+    }
+
+    fn process_raw(self) -> N {
+        self.seeds
+            .into_iter()
+            .map(move |seed| {
+                self.locations
+                    .iter()
+                    .fold(seed, |seed, location| location.map(seed))
+            })
+            .min()
+            .unwrap()
+    }
+
+    /// Search locations upwards for the first one coming from a seed of
+    /// `intervals`. Above every range and seed, numbers map to themselves and
+    /// come from no seed, so the search stops there.
+    fn process_range(&self, intervals: &[Range<N>]) -> Option<N> {
+        let bound = intervals
+            .iter()
+            .map(|range| range.end.clone())
+            .chain(self.locations.iter().flat_map(|location| {
+                location
+                    .ranges
+                    .iter()
+                    .flat_map(|range| [range.src_range().end, range.dst_range().end])
+            }))
+            .max()?;
+
+        let (_, src) = (0..)
+            .map(N::from_i64)
+            .take_while(|dst| *dst < bound)
+            .chunks(CHUNK_SIZE)
+            .into_iter()
+            .find_map(|chunk| {
+                let chunk: Vec<N> = chunk.collect();
+                par_iter!(chunk)
+                    .filter_map(|dst| {
+                        let src = self.get_src(dst);
+                        let seed = intervals.iter().any(|range| range.contains(&src));
+                        seed.then(|| (dst.clone(), src))
+                    })
+                    .min()
+            })?;
+        Some(self.get_dst(&src))
+    }
+
+    fn process_intervals(&self, intervals: Vec<Range<N>>) -> Option<N> {
+        self.locations
+            .iter()
+            .fold(intervals, |intervals, location| {
+                location.map_ranges(intervals)
+            })
+            .into_iter()
+            .map(|range| range.start)
+            .min()
+    }
+
+    /// The lowest location of the seeds of `part`.
+    fn lowest_location(&self, part: Part) -> Result<N> {
+        match part {
+            Part::One => Ok(self.clone().process_raw()),
+            Part::Two => {
+                let intervals = self.seed_intervals()?;
+                self.process_intervals(intervals).context("no seed range")
+            }
+        }
+    }
+}
+
+pub struct Day05;
+
+impl aoc::Solution for Day05 {
+    const DAY: u8 = 5;
+    type Model = Almanach;
+
+    fn parse(input: &str) -> Result<Self::Model> {
+        Almanach::parse(input)
+    }
+
+    fn part1(almanach: &Self::Model) -> Result<Answer> {
+        Ok(almanach.lowest_location(Part::One)?.into())
+    }
+
+    fn part2(almanach: &Self::Model) -> Result<Answer> {
+        Ok(almanach.lowest_location(Part::Two)?.into())
+    }
+}
+
+impl Numeric for Day05 {
+    fn solve_with<N: Int>(input: &str, part: Part) -> Result<Answer> {
+        let almanach = Almanach::<N>::parse(input)?;
+        Ok(almanach.lowest_location(part)?.into_answer())
+    }
+}
+
+impl Differential for Day05 {
+    fn alternatives(part: Part) -> Vec<Implementation<Self::Model>> {
+        match part {
+            Part::One => vec![Implementation::new("intervals", |almanach| {
+                let intervals = almanach.seeds.iter().map(|&seed| seed..seed + 1).collect();
+                let location = almanach.process_intervals(intervals).context("no seed")?;
+                Ok(location.into())
+            })],
+            Part::Two => vec![Implementation::new("reverse search", |almanach| {
+                let location = almanach
+                    .process_range(&almanach.seed_intervals()?)
+                    .context("no seed reachable")?;
+                Ok(location.into())
+            })],
+        }
+    }
+
+    /// Maps are permutations of `0..span`, as the reverse search of
+    /// `process_range` needs them to be invertible.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let span = 20 + 10 * size as u64;
+        let seeds = (0..rng.range(1..size.min(4) as u64 + 1))
+            .flat_map(|_| {
+                let start = rng.range(0..span);
+                [start, rng.range(1..span - start + 1)]
+            })
+            .join(" ");
+
+        let maps = SEED_STEPS
+            .into_iter()
+            .tuple_windows()
+            .map(|(from, to)| {
+                let cuts: Vec<u64> = (0..rng.range(0..size.min(6) as u64 + 1))
+                    .map(|_| rng.range(1..span))
+                    .chain([0, span])
+                    .sorted()
+                    .dedup()
+                    .collect();
+                let segments = cuts
+                    .iter()
+                    .tuple_windows()
+                    .map(|(a, b)| (a, b - a))
+                    .collect_vec();
+                let mut order = (0..segments.len()).collect_vec();
+                rng.shuffle(&mut order);
+                let mut dst = 0;
+                let mut lines = order
+                    .into_iter()
+                    .map(|i| {
+                        let (src, length) = segments[i];
+                        let line = format!("{} {} {}", dst, src, length);
+                        dst += length;
+                        line
+                    })
+                    .collect_vec();
+                rng.shuffle(&mut lines);
+                format!("{}-to-{} map:\n{}\n", from, to, lines.join("\n"))
+            })
+            .join("\n");
+
+        format!("seeds: {}\n\n{}", seeds, maps)
+    }
+
+    fn is_valid(almanach: &Self::Model) -> bool {
+        let merged = |ranges: Vec<Range<i64>>| {
+            ranges
+                .into_iter()
+                .sorted_by_key(|range| range.start)
+                .coalesce(|a, b| {
+                    if a.end == b.start {
+                        Ok(a.start..b.end)
+                    } else {
+                        Err((a, b))
+                    }
+                })
+                .collect_vec()
+        };
+        let disjoint = |ranges: &[Range<i64>]| {
+            ranges
+                .iter()
+                .sorted_by_key(|range| range.start)
+                .tuple_windows()
+                .all(|(a, b)| a.end <= b.start)
+        };
+        let permutations = almanach.locations.iter().all(|location| {
+            let src = location.ranges.iter().map(|r| r.src_range()).collect_vec();
+            let dst = location.ranges.iter().map(|r| r.dst_range()).collect_vec();
+            location.ranges.iter().all(|r| r.length > 0)
+                && disjoint(&src)
+                && disjoint(&dst)
+                && merged(src) == merged(dst)
+        });
+        permutations
+            && !almanach.seeds.is_empty()
+            && almanach.seeds.len() % 2 == 0
+            && almanach
+                .seeds
+                .iter()
+                .tuples()
+                .all(|(_, &length)| length > 0)
+    }
+
+    fn shrink(input: &str) -> Vec<String> {
+        let mut candidates = Vec::new();
+        let (seeds, maps) = input.split_once('\n').unwrap_or((input, ""));
+        let seeds = seeds.split_whitespace().collect_vec();
+        for i in (1..seeds.len()).step_by(2) {
+            let fewer = seeds
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i && j != i + 1)
+                .map(|(_, s)| s)
+                .join(" ");
+            candidates.push(format!("{}\n{}", fewer, maps));
+        }
+        candidates.extend(diff::shrink_text(input));
+        candidates
+    }
+}
+
+/// The reverse search of part 2 is left out, as it takes as many steps as
+/// the largest number of the input.
+impl aoc::fuzz::Fuzz for Day05 {
+    fn exercise(almanach: &Self::Model) {
+        almanach.clone().process_raw();
+        for seed in &almanach.seeds {
+            almanach.get_src(&almanach.get_dst(seed));
+        }
+        if let Ok(intervals) = almanach.seed_intervals() {
+            almanach.process_intervals(intervals);
+        }
+    }
+}
+
+/// `a + b`, or an error if it does not fit a `u64`.
+fn checked_sum(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b)
+        .with_context(|| format!("{} + {} overflows u64", a, b))
+}
+
+/// Each category is shifted by an offset that grows down the chain, the
+/// seeds by one above every number of the input. The lengths of the seed
+/// ranges are kept, as part 2 reads them, and the seed-to-soil map sends them,
+/// which part 1 reads as seeds, where it sent the original ones. Gaps of maps
+/// get explicit ranges so that unmapped numbers follow the shifts, and the
+/// numbers below the offset of a category come from numbers above every
+/// seed, so that the reverse search of part 2 does not find them.
+impl Anonymize for Day05 {
+    fn anonymize(input: &str, rng: &mut Rng) -> Result<Anonymized> {
+        let mut blocks = input.trim().split("\n\n");
+        let seeds: Vec<u64> = blocks
+            .next()
+            .and_then(|block| block.strip_prefix("seeds:"))
+            .context("missing seeds")?
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        let mut maps = Vec::new();
+        for (block, (from, to)) in blocks.zip(SEED_STEPS.into_iter().tuple_windows()) {
+            let (header, rows) = block.split_once('\n').unwrap_or((block, ""));
+            ensure!(
+                header == format!("{}-to-{} map:", from, to),
+                "expected the {}-to-{} map, found {:?}",
+                from,
+                to,
+                header
+            );
+            let ranges = rows
+                .lines()
+                .map(|row| {
+                    let numbers: Vec<u64> = row
+                        .split_whitespace()
+                        .map(str::parse)
+                        .collect::<Result<_, _>>()?;
+                    ensure!(numbers.len() == 3, "invalid range {:?}", row);
+                    Ok((numbers[0], numbers[1], numbers[2]))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            maps.push(ranges);
+        }
+        ensure!(
+            maps.len() == SEED_STEPS.len() - 1,
+            "expected {} maps, found {}",
+            SEED_STEPS.len() - 1,
+            maps.len()
+        );
+
+        // Every number reached from a seed is below.
+        let mut universe = 0;
+        for &seed in &seeds {
+            universe = universe.max(checked_sum(seed, 1)?);
+        }
+        for (&start, &length) in seeds.iter().tuples() {
+            universe = universe.max(checked_sum(start, length)?);
+        }
+        for &(dst, src, length) in maps.iter().flatten() {
+            universe = universe.max(checked_sum(dst.max(src), length)?);
+        }
+        let bound = checked_sum(universe, 1)?;
+        let mut offsets = vec![checked_sum(universe, rng.range(0..bound))?];
+        for _ in &maps {
+            let last = offsets[offsets.len() - 1];
+            offsets.push(checked_sum(last, rng.range(0..bound))?);
+        }
+
+        let maps = maps
+            .into_iter()
+            .zip(SEED_STEPS.into_iter().tuple_windows())
+            .enumerate()
+            .map(|(k, (ranges, (from, to)))| {
+                let (src_offset, dst_offset) = (offsets[k], offsets[k + 1]);
+                let mut rows = Vec::new();
+                let gap = |start: u64, end: u64, rows: &mut Vec<_>| -> Result<()> {
+                    if start < end {
+                        let dst = checked_sum(start, dst_offset)?;
+                        rows.push((dst, checked_sum(start, src_offset)?, end - start));
+                    }
+                    Ok(())
+                };
+                let mut next = 0;
+                for &(dst, src, length) in ranges.iter().sorted_by_key(|&(_, src, _)| src) {
+                    gap(next, src, &mut rows)?;
+                    let dst = checked_sum(dst, dst_offset)?;
+                    rows.push((dst, checked_sum(src, src_offset)?, length));
+                    next = next.max(src + length);
+                }
+                gap(next, universe, &mut rows)?;
+                if dst_offset > 0 {
+                    rows.push((0, checked_sum(src_offset, universe)?, dst_offset));
+                }
+                if k == 0 {
+                    for &length in seeds.iter().skip(1).step_by(2).sorted().dedup() {
+                        let mapped = ranges
+                            .iter()
+                            .find(|&&(_, src, n)| (src..src + n).contains(&length))
+                            .map_or(length, |&(dst, src, _)| dst + (length - src));
+                        rows.push((checked_sum(mapped, dst_offset)?, length, 1));
+                    }
+                }
+                rng.shuffle(&mut rows);
+                let rows = rows
+                    .iter()
+                    .map(|(dst, src, length)| format!("{} {} {}", dst, src, length))
+                    .join("\n");
+                Ok(format!("{}-to-{} map:\n{}", from, to, rows))
+            })
+            .collect::<Result<Vec<_>>>()?
+            .join("\n\n");
+
+        let seeds = seeds
+            .iter()
+            .enumerate()
+            .map(|(i, &seed)| match i % 2 {
+                0 => checked_sum(seed, offsets[0]),
+                _ => Ok(seed),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Anonymized {
+            input: format!("seeds: {}\n\n{}\n", seeds.iter().join(" "), maps),
+            shift: offsets[offsets.len() - 1].try_into()?,
+        })
+    }
+}
+
+impl Explore for Day05 {
+    fn commands() -> Vec<Command> {
+        vec![
+            Command::new("seeds", "", "list the seeds"),
+            Command::new(
+                "map",
+                "<category> <n>",
+                "follow n from category down to its location",
+            ),
+            Command::new(
+                "unmap",
+                "<category> <n>",
+                "follow n from category back up to its seed",
+            ),
+        ]
+    }
+
+    fn query(almanach: &Self::Model, command: &str, args: &[&str]) -> Result<String> {
+        if command == "seeds" {
+            return Ok(almanach.seeds.iter().join(" "));
+        }
+        let category: String = explore::arg(args, 0, "category")?;
+        let n: i64 = explore::arg(args, 1, "n")?;
+        let Some(step) = SEED_STEPS.iter().position(|&c| c == category) else {
+            bail!(
+                "unknown category {:?}, expected one of {}",
+                category,
+                SEED_STEPS.join(", ")
+            );
+        };
+        let mut chain = vec![format!("{} {}", category, n)];
+        let mut n = n;
+        match command {
+            "map" => {
+                for location in &almanach.locations[step..] {
+                    n = location.map(n);
+                    chain.push(format!("{} {}", location.to, n));
+                }
+            }
+            "unmap" => {
+                for location in almanach.locations[..step].iter().rev() {
+                    n = location.reverse_map(n);
+                    chain.push(format!("{} {}", location.from, n));
+                }
+            }
+            _ => bail!("unknown command {:?}", command),
+        }
+        Ok(chain.join(" -> "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use aoc::Solution;
+
+    #[test]
+    fn test_parse() {
+        let input = aoc::input::read("./data/test_input.txt").expect("failed to read input");
+        let almanach = Day05::parse(&input).unwrap();
+        assert_eq!(almanach.seeds, vec![79, 14, 55, 13]);
+    }
+
+    #[test]
+    fn test_p1() {
+        let input = aoc::input::read("./data/test_input.txt").expect("failed to read input");
+        let almanach = Day05::parse(&input).unwrap();
+        assert_eq!(almanach.process_raw(), 35);
+    }
+
+    #[test]
+    fn test_p2() {
+        let input = aoc::input::read("./data/test_input.txt").expect("failed to read input");
+        let almanach = Day05::parse(&input).unwrap();
+        assert_eq!(
+            almanach.process_range(&almanach.seed_intervals().unwrap()),
+            Some(46)
+        );
+        assert_eq!(almanach.process_range(&[]), None);
+    }
+
+    #[test]
+    fn test_map_range() {
+        let input = aoc::input::read("./data/test_input.txt").expect("failed to read input");
+        let almanach = Day05::parse(&input).unwrap();
+        let intervals: Vec<Range<i64>> = almanach
+            .seeds
+            .clone()
+            .into_iter()
+            .tuples()
+            .map(|(a, b)| a..a + b)
+            .collect();
+        let results = almanach
+            .locations
+            .iter()
+            .fold(intervals, |intervals, location| {
+                location.map_ranges(intervals)
+            });
+        println!("{:?}", results)
+    }
+
+    #[test]
+    fn test_p2_intervals() {
+        let input = aoc::input::read("./data/test_input.txt").expect("failed to read input");
+        let almanach = Day05::parse(&input).unwrap();
+        assert_eq!(
+            almanach.process_intervals(almanach.seed_intervals().unwrap()),
+            Some(46)
+        );
+    }
+
+    #[test]
+    fn test_explore() {
+        let input = aoc::input::read("./data/test_input.txt").expect("failed to read input");
+        let almanach = Day05::parse(&input).unwrap();
+        assert_eq!(
+            Day05::query(&almanach, "map", &["seed", "79"]).unwrap(),
+            "seed 79 -> soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 \
+             -> humidity 78 -> location 82"
+        );
+        assert_eq!(almanach.get_dst(&79), 82);
+        assert_eq!(
+            Day05::query(&almanach, "unmap", &["soil", "81"]).unwrap(),
+            "soil 81 -> seed 79"
+        );
+        assert!(Day05::query(&almanach, "map", &["pebble", "1"]).is_err());
+        assert!(Day05::query(&almanach, "map", &["seed"]).is_err());
+    }
+
+    #[test]
+    fn test_anonymize() {
+        let input = aoc::input::read("./data/test_input.txt").expect("failed to read input");
+        for seed in 0..5 {
+            let anonymized = Day05::anonymize(&input, &mut Rng::new(seed)).unwrap();
+            let almanach = Day05::parse(&anonymized.input).unwrap();
+            let starts = almanach.seeds.iter().step_by(2).collect_vec();
+            assert!(starts
+                .iter()
+                .all(|start| ![79, 14, 55, 13].contains(*start)));
+            let lengths = almanach.seeds.iter().skip(1).step_by(2).collect_vec();
+            assert_eq!(lengths, [&14, &13]);
+            let shift = anonymized.shift;
+            assert_eq!(almanach.clone().process_raw(), 35 + shift);
+            assert_eq!(
+                almanach.process_range(&almanach.seed_intervals().unwrap()),
+                Some(46 + shift)
+            );
+            assert_eq!(
+                almanach.process_intervals(almanach.seed_intervals().unwrap()),
+                Some(46 + shift)
+            );
+        }
+        assert!(
+            Day05::anonymize("seeds: 1 2\n\nsoil-to-seed map:\n1 2 3", &mut Rng::new(0)).is_err()
+        );
+        let huge = input.replacen("79", &(u64::MAX / 2).to_string(), 1);
+        let error = Day05::anonymize(&huge, &mut Rng::new(0)).unwrap_err();
+        assert!(error.to_string().ends_with("overflows u64"), "{}", error);
+    }
+
+    #[test]
+    fn test_differential() {
+        diff::assert_agree::<Day05>(&diff::Config {
+            cases: 30,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn test_examples() {
+        aoc::spec::assert_examples::<Day05>();
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        aoc::fuzz::assert_regressions::<Day05>();
+    }
+
+    #[test]
+    fn test_numbers() {
+        use aoc::num::{BigInt, Checked};
+
+        let input = aoc::input::read("./data/test_input.txt").expect("failed to read input");
+        for part in Part::ALL {
+            let answer = Day05::solve(&Day05::parse(&input).unwrap(), part).unwrap();
+            assert_eq!(Day05::solve_with::<i128>(&input, part).unwrap(), answer);
+            assert_eq!(Day05::solve_with::<BigInt>(&input, part).unwrap(), answer);
+            assert_eq!(
+                Day05::solve_with::<Checked<i64>>(&input, part).unwrap(),
+                answer
+            );
+        }
+
+        let (_, maps) = input.split_once('\n').unwrap();
+        let input = format!("seeds: {} 1 79 1\n{}", i64::MAX, maps);
+        let error = Day05::solve_with::<Checked<i64>>(&input, Part::Two).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "seed range 9223372036854775807 1 overflows i64"
+        );
+        assert_eq!(
+            Day05::solve_with::<i128>(&input, Part::Two).unwrap(),
+            Answer::Int(82)
+        );
+
+        let input = format!("seeds: {}0 1 79 1\n{}", i64::MAX, maps);
+        let error = Day05::solve_with::<i64>(&input, Part::One).unwrap_err();
+        assert!(format!("{:#}", error).starts_with("invalid i64"));
+        assert_eq!(
+            Day05::solve_with::<i128>(&input, Part::One).unwrap(),
+            Day05::solve_with::<BigInt>(&input, Part::One).unwrap()
+        );
+    }
+}
@@ -0,0 +1,274 @@
+#![allow(dead_code)]
+
+use anyhow::{bail, Context, Result};
+use aoc::diff::{Differential, Implementation, Rng};
+use aoc::{Answer, Part};
+use itertools::Itertools;
+
+/// The longest race, so that distances fit in a `u64`.
+const MAX_TIME: u64 = u32::MAX as u64;
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct RaceRecord {
+    time: u64,
+    rec_distance: u64,
+}
+
+#[derive(Debug)]
+enum Errors {
+    InvalidInput,
+    InvalidTime,
+}
+
+impl RaceRecord {
+    fn new(time: u64, distance: u64) -> RaceRecord {
+        RaceRecord {
+            time,
+            rec_distance: distance,
+        }
+    }
+
+    fn perform(&self, hold_time: u64) -> Result<u64, Errors> {
+        if self.time < hold_time {
+            return Err(Errors::InvalidTime);
+        }
+        let speed = hold_time;
+        let remain_time = self.time - hold_time;
+        Ok(speed * remain_time)
+    }
+
+    fn best_record_possible(&self) -> u64 {
+        (self.time / 2) * (self.time - self.time / 2)
+    }
+
+    fn nth_break_dist_rec(&self) -> u64 {
+        let all_hold_time: Vec<u64> = (0..=self.time).collect();
+        let half_best = all_hold_time[all_hold_time.len() / 2..]
+            .iter()
+            .map(|hold_time| self.perform(*hold_time).unwrap())
+            .take_while(|&r| r > self.rec_distance)
+            .count() as u64;
+        if half_best == 0 {
+            return 0;
+        }
+        half_best * 2 - (self.time + 1) % 2
+    }
+
+    fn beats_record(&self, hold_time: u64) -> bool {
+        self.perform(hold_time).is_ok_and(|d| d > self.rec_distance)
+    }
+
+    /// Same as `nth_break_dist_rec`, from the roots of
+    /// `hold_time * (time - hold_time) = rec_distance`.
+    fn nth_break_dist_quadratic(&self) -> u64 {
+        let (time, rec) = (self.time as f64, self.rec_distance as f64);
+        let root = (time - (time * time - 4. * rec).max(0.).sqrt()) / 2.;
+        let mut first = (root.max(0.) as u64).min(self.time / 2);
+        while first > 0 && self.beats_record(first - 1) {
+            first -= 1;
+        }
+        while first <= self.time / 2 && !self.beats_record(first) {
+            first += 1;
+        }
+        if first > self.time / 2 {
+            return 0;
+        }
+        self.time + 1 - 2 * first
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Races {
+    records: Vec<RaceRecord>,
+}
+
+impl From<Vec<RaceRecord>> for Races {
+    fn from(input: Vec<RaceRecord>) -> Self {
+        Self::new(input)
+    }
+}
+
+impl Races {
+    fn new(records: Vec<RaceRecord>) -> Races {
+        Races { records }
+    }
+
+    fn times(&self) -> Vec<u64> {
+        self.records.iter().map(|r| r.time).collect()
+    }
+
+    fn distances(&self) -> Vec<u64> {
+        self.records.iter().map(|r| r.rec_distance).collect()
+    }
+
+    fn nth_break_prod(&self) -> u64 {
+        self.records
+            .iter()
+            .map(|r| r.nth_break_dist_rec())
+            .product::<u64>()
+    }
+
+    fn nth_break_prod_quadratic(&self) -> u64 {
+        self.records
+            .iter()
+            .map(|r| r.nth_break_dist_quadratic())
+            .product::<u64>()
+    }
+
+    /// The single race obtained by ignoring the spaces between numbers, if
+    /// it is not longer than [`MAX_TIME`].
+    fn unkerned(&self) -> Option<RaceRecord> {
+        let time = self.times().iter().join("").parse().ok()?;
+        if time > MAX_TIME {
+            return None;
+        }
+        let distance = self.distances().iter().join("").parse().ok()?;
+        Some(RaceRecord::new(time, distance))
+    }
+
+    fn parse(input: &str) -> Result<Races> {
+        let (times_line, records_line) = input
+            .lines()
+            .next_tuple()
+            .context("expected a line of times and a line of distances")?;
+        let numbers = |line: &str, prefix: &str| -> Result<Vec<u64>> {
+            line.strip_prefix(prefix)
+                .with_context(|| format!("expected {:?} in {:?}", prefix, line))?
+                .split_whitespace()
+                .map(|n| n.parse().with_context(|| format!("invalid number {:?}", n)))
+                .collect()
+        };
+        let times = numbers(times_line, "Time:")?;
+        if let Some(time) = times.iter().find(|&&t| t > MAX_TIME) {
+            bail!("race of {} ms is longer than {} ms", time, MAX_TIME);
+        }
+        let distances = numbers(records_line, "Distance:")?;
+
+        Ok(times
+            .into_iter()
+            .zip(distances)
+            .map(|(time, distance)| RaceRecord::new(time, distance))
+            .collect::<Vec<_>>()
+            .into())
+    }
+}
+
+pub struct Day06;
+
+impl aoc::Solution for Day06 {
+    const DAY: u8 = 6;
+    type Model = Races;
+
+    fn parse(input: &str) -> Result<Self::Model> {
+        Races::parse(input)
+    }
+
+    fn part1(races: &Self::Model) -> Result<Answer> {
+        Ok(races.nth_break_prod().into())
+    }
+
+    fn part2(races: &Self::Model) -> Result<Answer> {
+        let race = races.unkerned().context("no race")?;
+        Ok(race.nth_break_dist_rec().into())
+    }
+}
+
+impl Differential for Day06 {
+    fn alternatives(part: Part) -> Vec<Implementation<Self::Model>> {
+        match part {
+            Part::One => vec![Implementation::new("quadratic", |races| {
+                Ok(races.nth_break_prod_quadratic().into())
+            })],
+            Part::Two => vec![Implementation::new("quadratic", |races| {
+                let race = races.unkerned().context("no race")?;
+                Ok(race.nth_break_dist_quadratic().into())
+            })],
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        loop {
+            let records = (0..rng.range(1..size.min(3) as u64 + 1))
+                .map(|_| {
+                    let time = rng.range(1..10 * size.min(9) as u64 + 1);
+                    let best = RaceRecord::new(time, 0).best_record_possible();
+                    RaceRecord::new(time, rng.range(0..best.max(1)))
+                })
+                .collect_vec();
+            let races = Races::new(records);
+            if Self::is_valid(&races) {
+                return format!(
+                    "Time: {}\nDistance: {}\n",
+                    races.times().iter().join(" "),
+                    races.distances().iter().join(" ")
+                );
+            }
+        }
+    }
+
+    /// Every race, including the unkerned one, can be won.
+    fn is_valid(races: &Self::Model) -> bool {
+        races
+            .records
+            .iter()
+            .chain(races.unkerned().as_ref())
+            .all(|r| r.rec_distance < r.best_record_possible())
+    }
+}
+
+/// `nth_break_dist_rec` allocates one slot per hold time, so it only runs
+/// on short races.
+impl aoc::fuzz::Fuzz for Day06 {
+    fn exercise(races: &Self::Model) {
+        for race in races.records.iter().chain(races.unkerned().as_ref()) {
+            race.best_record_possible();
+            race.nth_break_dist_quadratic();
+            if race.time <= 1_000_000 {
+                race.nth_break_dist_rec();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let races = Races::parse(&aoc::input::read("./data/test_input.txt").unwrap()).unwrap();
+        assert_eq!(races.times(), vec![7, 15, 30]);
+        assert_eq!(races.distances(), vec![9, 40, 200]);
+    }
+
+    #[test]
+    fn test_p1() {
+        let races = Races::parse(&aoc::input::read("./data/test_input.txt").unwrap()).unwrap();
+        println!("{:?}", races.nth_break_prod());
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let races = Races::parse(&aoc::input::read("./data/test_input.txt").unwrap()).unwrap();
+        assert_eq!(races.nth_break_prod_quadratic(), 288);
+        assert_eq!(races.unkerned().unwrap().nth_break_dist_quadratic(), 71503);
+    }
+
+    #[test]
+    fn test_differential() {
+        aoc::diff::assert_agree::<Day06>(&Default::default());
+    }
+
+    #[test]
+    fn test_examples() {
+        aoc::spec::assert_examples::<Day06>();
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        aoc::fuzz::assert_regressions::<Day06>();
+    }
+}
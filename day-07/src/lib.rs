@@ -0,0 +1,342 @@
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+use anyhow::{anyhow, bail, Context, Result};
+use aoc::num::{Int, Numeric};
+use aoc::stream::{self, Stream};
+use aoc::{Answer, Part};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+enum Card {
+    Joker,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl TryFrom<char> for Card {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        Ok(match c {
+            '2' => Card::Two,
+            '3' => Card::Three,
+            '4' => Card::Four,
+            '5' => Card::Five,
+            '6' => Card::Six,
+            '7' => Card::Seven,
+            '8' => Card::Eight,
+            '9' => Card::Nine,
+            'T' => Card::Ten,
+            'J' => Card::Jack,
+            'Q' => Card::Queen,
+            'K' => Card::King,
+            'A' => Card::Ace,
+            _ => bail!("invalid card {:?}", c),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum HandType {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeKind,
+    FullHouse,
+    FourKind,
+    FiveKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Cards([Card; 5]);
+
+impl From<&Cards> for HandType {
+    fn from(cards: &Cards) -> Self {
+        let mut counter = cards.0.iter().counts();
+        let n_joker = counter.remove(&Card::Joker).unwrap_or(0);
+        if n_joker == 5 {
+            return HandType::FiveKind;
+        }
+
+        let mut max_nvals = counter.values().sorted().rev();
+        let best_nval = *max_nvals.next().unwrap() + n_joker;
+        if best_nval == 5 {
+            return HandType::FiveKind;
+        } else if best_nval == 4 {
+            return HandType::FourKind;
+        }
+        let second_nval = *max_nvals.next().unwrap();
+        if best_nval == 3 && second_nval == 2 {
+            return HandType::FullHouse;
+        }
+
+        if best_nval == 3 {
+            return HandType::ThreeKind;
+        } else if best_nval == 2 && second_nval == 2 {
+            return HandType::TwoPair;
+        } else if best_nval == 2 {
+            return HandType::Pair;
+        }
+
+        HandType::HighCard
+    }
+}
+
+impl HandType {
+    /// The type of a hand such as `"32T3K"`, where a `J` is a joker if
+    /// `jokers` is set.
+    pub fn of(cards: &str, jokers: bool) -> Result<Self> {
+        let cards = Cards::parse(cards)?;
+        let cards = if jokers { cards.with_jokers() } else { cards };
+        Ok(HandType::from(&cards))
+    }
+}
+
+impl Cards {
+    /// Five cards such as `"32T3K"`.
+    fn parse(s: &str) -> Result<Self> {
+        let cards: Vec<Card> = s
+            .chars()
+            .map(Card::try_from)
+            .collect::<Result<_>>()
+            .with_context(|| format!("invalid hand {:?}", s))?;
+        let cards = cards
+            .try_into()
+            .map_err(|_| anyhow!("invalid hand {:?}, expected 5 cards", s))?;
+        Ok(Self(cards))
+    }
+
+    fn with_jokers(self) -> Self {
+        Self(self.0.map(|card| match card {
+            Card::Jack => Card::Joker,
+            card => card,
+        }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Hand {
+    hand_type: HandType,
+    cards: Cards,
+    bid: u32,
+}
+
+impl Hand {
+    fn new(cards: Cards, bid: Option<u32>) -> Self {
+        Self {
+            hand_type: (&cards).into(),
+            cards,
+            bid: bid.unwrap_or(0),
+        }
+    }
+    fn parse(row: &str) -> Result<Self> {
+        let mut col_iter = row.split_whitespace();
+        let cards = col_iter
+            .next()
+            .with_context(|| format!("no hand in {:?}", row))?;
+        let bid = col_iter
+            .next()
+            .map(|bid| bid.parse::<u32>())
+            .transpose()
+            .with_context(|| format!("invalid bid in {:?}", row))?;
+        Ok(Self::new(Cards::parse(cards)?, bid))
+    }
+
+    /// The same hand, where jacks are jokers.
+    fn with_jokers(&self) -> Self {
+        Self::new(self.cards.with_jokers(), Some(self.bid))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Hands(Vec<Hand>);
+
+impl Hands {
+    fn new(hands: Vec<Hand>) -> Self {
+        Self(hands)
+    }
+
+    fn parse(input: &str) -> Result<Self> {
+        let hands: Vec<Hand> = input.lines().map(Hand::parse).collect::<Result<_>>()?;
+        Ok(Hands::new(hands.into_iter().sorted().collect()))
+    }
+
+    fn from_file(path: &str) -> Self {
+        let contents = aoc::input::read(path).unwrap();
+        Self::parse(&contents).unwrap()
+    }
+
+    fn with_jokers(&self) -> Self {
+        Hands::new(self.0.iter().map(Hand::with_jokers).collect())
+    }
+
+    /// The total winnings, each hand winning its bid times its rank.
+    fn score<N: Int>(&self) -> N {
+        self.0
+            .iter()
+            .sorted()
+            .enumerate()
+            .map(|(i, hand)| N::from_i64(i as i64 + 1) * N::from_i64(hand.bid.into()))
+            .sum()
+    }
+}
+
+pub struct Day07;
+
+impl aoc::Solution for Day07 {
+    const DAY: u8 = 7;
+    type Model = Hands;
+
+    fn parse(input: &str) -> Result<Self::Model> {
+        Hands::parse(input)
+    }
+
+    fn part1(hands: &Self::Model) -> Result<Answer> {
+        Ok(hands.score::<i64>().into())
+    }
+
+    fn part2(hands: &Self::Model) -> Result<Answer> {
+        Ok(hands.with_jokers().score::<i64>().into())
+    }
+}
+
+impl Numeric for Day07 {
+    fn solve_with<N: Int>(input: &str, part: Part) -> Result<Answer> {
+        let hands = Hands::parse(input)?;
+        let hands = match part {
+            Part::One => hands,
+            Part::Two => hands.with_jokers(),
+        };
+        Ok(hands.score::<N>().into_answer())
+    }
+}
+
+/// Ranks depend on every hand, so the hands are counted rather than kept:
+/// memory grows with the distinct hands and bids, not with the lines.
+impl Stream for Day07 {
+    fn solve_stream(input: &mut dyn BufRead, part: Part) -> Result<Answer> {
+        let mut counts: BTreeMap<Hand, i64> = BTreeMap::new();
+        for line in stream::lines(input) {
+            let hand = Hand::parse(&line?)?;
+            let hand = match part {
+                Part::One => hand,
+                Part::Two => hand.with_jokers(),
+            };
+            *counts.entry(hand).or_default() += 1;
+        }
+        let mut rank: i64 = 1;
+        let mut winnings: i64 = 0;
+        for (hand, count) in counts {
+            // The ranks from `rank` to `rank + count - 1`, summed.
+            winnings += i64::from(hand.bid) * (rank * count + count * (count - 1) / 2);
+            rank += count;
+        }
+        Ok(winnings.into())
+    }
+}
+
+impl aoc::fuzz::Fuzz for Day07 {
+    fn exercise(hands: &Self::Model) {
+        hands.score::<i64>();
+        hands.with_jokers().score::<i64>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hand_type() {
+        let hand = Hand::parse("32T3K").unwrap();
+        assert_eq!(hand.hand_type, HandType::Pair);
+
+        let hand = Hand::parse("QQQJA").unwrap();
+        assert_eq!(hand.hand_type, HandType::ThreeKind);
+        assert_eq!(hand.with_jokers().hand_type, HandType::FourKind);
+
+        assert_eq!(HandType::of("QQQJA", true).unwrap(), HandType::FourKind);
+        assert_eq!(HandType::of("JJJJJ", true).unwrap(), HandType::FiveKind);
+        assert!(HandType::of("QQQJ", false).is_err());
+        assert!(HandType::of("QQQJ1", false).is_err());
+    }
+
+    #[test]
+    fn test_part1() {
+        let hands = Hands::from_file("./data/test_input.txt");
+        println!("{:?}", hands);
+        println!("{}", hands.score::<i64>());
+        assert_eq!(hands.score::<i64>(), 6440);
+    }
+
+    #[test]
+    fn test_part2() {
+        let hands = Hands::from_file("./data/test_input.txt");
+        assert_eq!(hands.with_jokers().score::<i64>(), 5905);
+    }
+
+    #[test]
+    fn test_examples() {
+        aoc::spec::assert_examples::<Day07>();
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        aoc::fuzz::assert_regressions::<Day07>();
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = "32T3K 765\nT55J5 684\nKK677 28\nKK677 28\nKTJJT 220\nQQQJA 483\n";
+        let hands = Hands::parse(input).unwrap();
+        for part in Part::ALL {
+            let expected = match part {
+                Part::One => hands.score::<i64>(),
+                Part::Two => hands.with_jokers().score::<i64>(),
+            };
+            assert_eq!(
+                Day07::solve_stream(&mut input.as_bytes(), part).unwrap(),
+                Answer::Int(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        use aoc::num::{BigInt, Checked};
+
+        let input = "32T3K 4000000000\nKK677 4000000000\n";
+        let expected = Answer::Int(12_000_000_000);
+        assert_eq!(
+            Day07::solve_with::<i64>(input, Part::One).unwrap(),
+            expected
+        );
+        assert_eq!(
+            Day07::solve_with::<Checked<i128>>(input, Part::One).unwrap(),
+            expected
+        );
+        assert_eq!(
+            Day07::solve_with::<BigInt>(input, Part::One).unwrap(),
+            expected
+        );
+    }
+}
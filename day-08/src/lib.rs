@@ -0,0 +1,369 @@
+#![allow(dead_code)]
+
+use anyhow::{bail, ensure, Context, Result};
+use aoc::anonymize::{Anonymize, Anonymized};
+use aoc::diff::Rng;
+use aoc::explore::{self, Command, Explore};
+use aoc::{par_iter, Answer};
+use num::Integer;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Node {
+    name: String,
+    left: String,
+    right: String,
+}
+
+impl Node {
+    fn new(name: String, left: String, right: String) -> Self {
+        Self { name, left, right }
+    }
+
+    fn parse(input: &str) -> Result<Self> {
+        let re = Regex::new(r"(\w+) = \((\w+), (\w+)\)").unwrap();
+        let cap = re
+            .captures(input)
+            .with_context(|| format!("invalid node {:?}", input))?;
+        let (name, left, right) = cap
+            .iter()
+            .skip(1)
+            .map(|x| x.unwrap().as_str().to_string())
+            .next_tuple()
+            .unwrap();
+        Ok(Self::new(name, left, right))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+enum Direction {
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(input: char) -> Result<Self> {
+        match input {
+            'L' => Ok(Self::Left),
+            'R' => Ok(Self::Right),
+            c => bail!("invalid direction {:?}, expected L or R", c),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Network {
+    path: Vec<Direction>,
+    nodes: HashMap<String, Node>,
+}
+
+impl Network {
+    fn new(path: Vec<Direction>, nodes: Vec<Node>) -> Self {
+        Self {
+            path,
+            nodes: HashMap::from_iter(
+                nodes
+                    .iter()
+                    .map(|node| (node.name.to_string(), node.clone())),
+            ),
+        }
+    }
+
+    /// The network of `input`, whose path is not empty and whose nodes only
+    /// lead to nodes of the network.
+    fn parse(input: &str) -> Result<Self> {
+        let mut lines = input.lines();
+        let directions: Vec<Direction> = lines
+            .next()
+            .context("missing the path")?
+            .chars()
+            .map(Direction::try_from)
+            .collect::<Result<_>>()?;
+        ensure!(!directions.is_empty(), "empty path");
+        let nodes: Vec<Node> = lines.skip(1).map(Node::parse).collect::<Result<_>>()?;
+        let network = Self::new(directions, nodes);
+        for node in network.nodes.values() {
+            for next in [&node.left, &node.right] {
+                ensure!(network.nodes.contains_key(next), "no node {:?}", next);
+            }
+        }
+        Ok(network)
+    }
+
+    fn from_file(path: &str) -> Self {
+        Network::parse(aoc::input::read(path).unwrap().as_str()).unwrap()
+    }
+
+    fn get_node(&self, name: &str) -> &Node {
+        self.nodes.get(name).unwrap()
+    }
+
+    fn find_next(&self, name: &str, direction: &Direction) -> String {
+        let node = self.get_node(name);
+        match direction {
+            Direction::Left => node.left.clone(),
+            Direction::Right => node.right.clone(),
+        }
+    }
+
+    fn walk_from(&self, start: &str, target: &str) -> usize {
+        let mut current_node = start.to_string();
+        for (step, direction) in self.path.iter().cycle().enumerate() {
+            current_node = self.find_next(&current_node, direction);
+            if current_node == target {
+                return step + 1;
+            }
+        }
+        unreachable!();
+    }
+
+    fn walk_ghosts(&self, start: &str) -> usize {
+        let mut current_node = start.to_string();
+        for (step, direction) in self.path.iter().cycle().enumerate() {
+            current_node = self.find_next(&current_node, direction);
+            if current_node.ends_with('Z') {
+                return step + 1;
+            }
+        }
+        unreachable!();
+    }
+
+    fn find_steps_ghosts(&self) -> usize {
+        let steps: Vec<usize> = par_iter!(self.nodes)
+            .filter(|(x, _)| x.ends_with('A'))
+            .map(|(x, _)| self.walk_ghosts(x))
+            .collect();
+        steps.into_iter().fold(1, |a, b| a.lcm(&b))
+    }
+}
+
+pub struct Day08;
+
+impl aoc::Solution for Day08 {
+    const DAY: u8 = 8;
+    type Model = Network;
+
+    fn parse(input: &str) -> Result<Self::Model> {
+        Network::parse(input)
+    }
+
+    fn part1(network: &Self::Model) -> Result<Answer> {
+        ensure!(network.nodes.contains_key("AAA"), "no node \"AAA\"");
+        Ok(network.walk_from("AAA", "ZZZ").into())
+    }
+
+    fn part2(network: &Self::Model) -> Result<Answer> {
+        Ok(network.find_steps_ghosts().into())
+    }
+}
+
+/// Walks may never reach their target, so each node only follows the path
+/// once.
+impl aoc::fuzz::Fuzz for Day08 {
+    fn exercise(network: &Self::Model) {
+        for name in network.nodes.keys() {
+            network.path.iter().fold(name.clone(), |node, direction| {
+                network.find_next(&node, direction)
+            });
+        }
+    }
+}
+
+/// Nodes get random names of three letters, keeping `AAA`, `ZZZ` and whether
+/// names end with `A` or `Z`. Left and right may also be swapped throughout.
+impl Anonymize for Day08 {
+    fn anonymize(input: &str, rng: &mut Rng) -> Result<Anonymized> {
+        const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        let mut lines = input.lines();
+        let path = lines.next().context("missing the path")?;
+        ensure!(
+            path.chars().all(|c| c == 'L' || c == 'R'),
+            "invalid path {:?}",
+            path
+        );
+        let nodes: Vec<Node> = lines
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (name, rest) = line.split_once(" = (").context("invalid node")?;
+                let (left, right) = rest
+                    .strip_suffix(')')
+                    .and_then(|rest| rest.split_once(", "))
+                    .with_context(|| format!("invalid node {:?}", line))?;
+                Ok(Node::new(name.into(), left.into(), right.into()))
+            })
+            .collect::<Result<_>>()?;
+
+        let mut names = HashMap::from([("AAA", "AAA".to_string()), ("ZZZ", "ZZZ".to_string())]);
+        let mut taken: HashSet<String> = names.values().cloned().collect();
+        for node in &nodes {
+            for name in [&node.name, &node.left, &node.right] {
+                if names.contains_key(name.as_str()) {
+                    continue;
+                }
+                let renamed = loop {
+                    let last = match name.chars().last() {
+                        Some(c @ ('A' | 'Z')) => c,
+                        _ => *rng.choose(&LETTERS[1..LETTERS.len() - 1]) as char,
+                    };
+                    let renamed: String = (0..2)
+                        .map(|_| *rng.choose(LETTERS) as char)
+                        .chain([last])
+                        .collect();
+                    if !taken.contains(&renamed) {
+                        break renamed;
+                    }
+                };
+                taken.insert(renamed.clone());
+                names.insert(name, renamed);
+            }
+        }
+
+        let swap = rng.one_in(2);
+        let path: String = path
+            .chars()
+            .map(|c| match (c, swap) {
+                ('L', true) => 'R',
+                ('R', true) => 'L',
+                (c, _) => c,
+            })
+            .collect();
+        let mut lines: Vec<String> = nodes
+            .iter()
+            .map(|node| {
+                let (left, right) = match swap {
+                    true => (&node.right, &node.left),
+                    false => (&node.left, &node.right),
+                };
+                format!(
+                    "{} = ({}, {})",
+                    names[node.name.as_str()],
+                    names[left.as_str()],
+                    names[right.as_str()]
+                )
+            })
+            .collect();
+        rng.shuffle(&mut lines);
+        Ok(Anonymized::same(format!(
+            "{}\n\n{}\n",
+            path,
+            lines.join("\n")
+        )))
+    }
+}
+
+impl Explore for Day08 {
+    fn commands() -> Vec<Command> {
+        vec![
+            Command::new("path", "", "show the left/right instructions"),
+            Command::new("node", "<name>", "show where a node leads"),
+            Command::new("next", "<name> <L|R>", "take one step from a node"),
+        ]
+    }
+
+    fn query(network: &Self::Model, command: &str, args: &[&str]) -> Result<String> {
+        if command == "path" {
+            let path: String = network
+                .path
+                .iter()
+                .map(|d| match d {
+                    Direction::Left => 'L',
+                    Direction::Right => 'R',
+                })
+                .collect();
+            return Ok(format!("{} ({} steps)", path, path.len()));
+        }
+        let name: String = explore::arg(args, 0, "name")?;
+        let Some(node) = network.nodes.get(&name) else {
+            bail!("no node {:?}", name);
+        };
+        match command {
+            "node" => Ok(format!("{} = ({}, {})", node.name, node.left, node.right)),
+            "next" => {
+                let direction = Direction::try_from(explore::arg::<char>(args, 1, "L|R")?)?;
+                Ok(network.find_next(&name, &direction))
+            }
+            _ => bail!("unknown command {:?}", command),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let network = Network::from_file("data/test_input_1.txt");
+        assert_eq!(network.walk_from("AAA", "ZZZ"), 2);
+
+        let network = Network::from_file("data/test_input_2.txt");
+        assert_eq!(network.walk_from("AAA", "ZZZ"), 6);
+    }
+
+    #[test]
+    fn test_explore() {
+        let network = Network::from_file("data/test_input_1.txt");
+        assert_eq!(
+            Day08::query(&network, "next", &["AAA", "L"]).unwrap(),
+            "BBB"
+        );
+        assert_eq!(
+            Day08::query(&network, "next", &["AAA", "R"]).unwrap(),
+            "CCC"
+        );
+        assert_eq!(
+            Day08::query(&network, "node", &["AAA"]).unwrap(),
+            "AAA = (BBB, CCC)"
+        );
+        assert_eq!(Day08::query(&network, "path", &[]).unwrap(), "RL (2 steps)");
+        assert!(Day08::query(&network, "next", &["AAA", "X"]).is_err());
+        assert!(Day08::query(&network, "next", &["QQQ", "L"]).is_err());
+    }
+
+    #[test]
+    fn test_part2() {
+        let network = Network::from_file("data/test_input_3.txt");
+        assert_eq!(6, network.find_steps_ghosts());
+    }
+
+    #[test]
+    fn test_anonymize() {
+        for seed in 0..5 {
+            let mut rng = Rng::new(seed);
+            let input = aoc::input::read("data/test_input_2.txt").unwrap();
+            let anonymized = Day08::anonymize(&input, &mut rng).unwrap();
+            assert_eq!(anonymized.shift, 0);
+            let network = Network::parse(&anonymized.input).unwrap();
+            assert_eq!(network.nodes.len(), 3);
+            assert_eq!(network.walk_from("AAA", "ZZZ"), 6);
+
+            let input = aoc::input::read("data/test_input_3.txt").unwrap();
+            let anonymized = Day08::anonymize(&input, &mut rng).unwrap();
+            let network = Network::parse(&anonymized.input).unwrap();
+            assert!(!network.nodes.contains_key("XXX"));
+            assert_eq!(network.find_steps_ghosts(), 6);
+        }
+        assert!(Day08::anonymize("LR\n\nAAA = BBB", &mut Rng::new(0)).is_err());
+    }
+
+    #[test]
+    fn test_examples() {
+        aoc::spec::assert_examples::<Day08>();
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        aoc::fuzz::assert_regressions::<Day08>();
+    }
+}
@@ -0,0 +1,178 @@
+use std::io::BufRead;
+
+use anyhow::{ensure, Result};
+use aoc::num::{BigInt, Int, Numeric};
+use aoc::stream::{self, Stream};
+use aoc::{par_iter, par_lines, Answer, Part};
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+
+fn find_diffs<N: Int>(history: Vec<N>) -> Vec<Vec<N>> {
+    let mut diffs: Vec<Vec<N>> = Vec::new();
+    diffs.push(history);
+    while diffs
+        .last()
+        .is_some_and(|x| x.len() > 1 && x.iter().any(|x| *x != N::zero()))
+    {
+        let last_diff = diffs.last().unwrap();
+        diffs.push(
+            last_diff
+                .iter()
+                .zip(last_diff.iter().skip(1))
+                .map(|(a, b)| b.clone() - a.clone())
+                .collect::<Vec<N>>(),
+        );
+    }
+    diffs
+}
+
+/// The value following a non-empty `history`.
+pub fn extrapolate_pred<N: Int>(history: Vec<N>) -> N {
+    let diffs = find_diffs(history);
+    diffs.iter().map(|x| x.last().unwrap().clone()).rev().sum()
+}
+
+/// The value preceding a non-empty `history`.
+pub fn extrapolate_pred_backward<N: Int>(history: Vec<N>) -> N {
+    let diffs = find_diffs(history);
+    diffs
+        .iter()
+        .map(|x| x.first().unwrap().clone())
+        .rev()
+        .fold(N::zero(), |a, b| b - a)
+}
+
+fn parse_line<N: Int>(line: &str) -> Result<Vec<N>> {
+    let history: Vec<N> = line
+        .split_whitespace()
+        .map(N::parse)
+        .collect::<Result<_>>()?;
+    ensure!(!history.is_empty(), "empty history");
+    Ok(history)
+}
+
+/// The histories of `input`, failing on its first invalid line.
+fn parse_history<N: Int>(input: &str) -> Result<Vec<Vec<N>>> {
+    let lines: Vec<Result<Vec<N>>> = par_lines!(input).map(parse_line).collect();
+    lines.into_iter().collect()
+}
+
+/// The sum of the values extrapolated from each history, for `part`, added
+/// in order so that checked integers overflow the same when parallel.
+fn extrapolate_all<N: Int>(history: &[Vec<N>], part: Part) -> N {
+    let extrapolate = match part {
+        Part::One => extrapolate_pred,
+        Part::Two => extrapolate_pred_backward,
+    };
+    let values: Vec<N> = par_iter!(history)
+        .map(|x| extrapolate(x.to_vec()))
+        .collect();
+    values.into_iter().sum()
+}
+
+pub struct Day09;
+
+impl aoc::Solution for Day09 {
+    const DAY: u8 = 9;
+    type Model = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Model> {
+        parse_history(input)
+    }
+
+    fn part1(history: &Self::Model) -> Result<Answer> {
+        Ok(extrapolate_all(history, Part::One).into())
+    }
+
+    fn part2(history: &Self::Model) -> Result<Answer> {
+        Ok(extrapolate_all(history, Part::Two).into())
+    }
+}
+
+impl Numeric for Day09 {
+    fn solve_with<N: Int>(input: &str, part: Part) -> Result<Answer> {
+        let history = parse_history::<N>(input)?;
+        Ok(extrapolate_all(&history, part).into_answer())
+    }
+}
+
+impl Stream for Day09 {
+    fn solve_stream(input: &mut dyn BufRead, part: Part) -> Result<Answer> {
+        let mut sum = 0;
+        for line in stream::lines(input) {
+            sum += extrapolate_all(&[parse_line::<i64>(&line?)?], part);
+        }
+        Ok(sum.into())
+    }
+}
+
+/// Differences of `i64` overflow quickly, which `--checked` reports, so
+/// histories are extrapolated with big integers.
+impl aoc::fuzz::Fuzz for Day09 {
+    fn exercise(history: &Self::Model) {
+        for values in history {
+            let values: Vec<BigInt> = values.iter().map(|&x| BigInt::from_i64(x)).collect();
+            extrapolate_pred(values.clone());
+            extrapolate_pred_backward(values);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        aoc::spec::assert_examples::<Day09>();
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        aoc::fuzz::assert_regressions::<Day09>();
+    }
+
+    #[test]
+    fn test_stream() {
+        use aoc::Solution;
+
+        let path = "data/test_input.txt";
+        let history = Day09::parse(&aoc::input::read(path).unwrap()).unwrap();
+        for part in Part::ALL {
+            let mut input = aoc::input::open(path).unwrap();
+            assert_eq!(
+                Day09::solve_stream(&mut input, part).unwrap(),
+                Day09::solve(&history, part).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        use aoc::num::{BigInt, Checked};
+
+        let input = aoc::input::read("data/test_input.txt").unwrap();
+        for part in Part::ALL {
+            let answer = Day09::solve_with::<i64>(&input, part).unwrap();
+            assert_eq!(Day09::solve_with::<i128>(&input, part).unwrap(), answer);
+            assert_eq!(Day09::solve_with::<BigInt>(&input, part).unwrap(), answer);
+            assert_eq!(
+                Day09::solve_with::<Checked<i64>>(&input, part).unwrap(),
+                answer
+            );
+        }
+
+        let step = i64::MAX / 2;
+        let input = format!("0 {} {}\n", step, 2 * step);
+        let message =
+            aoc::diff::catch(|| Day09::solve_with::<Checked<i64>>(&input, Part::One)).unwrap_err();
+        assert_eq!(
+            message,
+            "i64 overflow: 4611686018427387903 + 9223372036854775806"
+        );
+        assert_eq!(
+            Day09::solve_with::<BigInt>(&input, Part::One).unwrap(),
+            Answer::from(3 * i128::from(step))
+        );
+    }
+}
@@ -0,0 +1,698 @@
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+
+use anyhow::{bail, ensure, Context, Result};
+use aoc::diff::{Differential, Implementation, Rng};
+use aoc::explore::{self, Command, Explore};
+use aoc::trace::{self, Event};
+use aoc::{par_iter, Answer, Part};
+use geo::Contains;
+use geo_types::{LineString, Point, Polygon};
+use itertools::Itertools;
+use ndarray::Array2;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+enum PipeKind {
+    P([Direction; 2]),
+    G,
+    S,
+}
+
+impl TryFrom<char> for PipeKind {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        Ok(match c {
+            '|' => PipeKind::P([Direction::North, Direction::South]),
+            '-' => PipeKind::P([Direction::West, Direction::East]),
+            'L' => PipeKind::P([Direction::North, Direction::East]),
+            'J' => PipeKind::P([Direction::North, Direction::West]),
+            '7' => PipeKind::P([Direction::South, Direction::West]),
+            'F' => PipeKind::P([Direction::South, Direction::East]),
+            'S' => PipeKind::S,
+            '.' => PipeKind::G,
+            _ => bail!("invalid tile {:?}", c),
+        })
+    }
+}
+
+impl PipeKind {
+    fn symbol(&self) -> char {
+        match self {
+            PipeKind::P([Direction::North, Direction::South]) => '|',
+            PipeKind::P([Direction::West, Direction::East]) => '-',
+            PipeKind::P([Direction::North, Direction::East]) => 'L',
+            PipeKind::P([Direction::North, Direction::West]) => 'J',
+            PipeKind::P([Direction::South, Direction::West]) => '7',
+            PipeKind::P([Direction::South, Direction::East]) => 'F',
+            PipeKind::P(_) => '?',
+            PipeKind::G => '.',
+            PipeKind::S => 'S',
+        }
+    }
+
+    fn traverse(&self, from: Direction) -> Option<Direction> {
+        match self {
+            PipeKind::P([d1, d2]) => {
+                if from != *d1 && from != *d2 {
+                    None
+                } else {
+                    Some(if from == *d1 { *d2 } else { *d1 })
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Pipe {
+    kind: PipeKind,
+    pos: (usize, usize),
+}
+
+impl Pipe {
+    fn new(kind: PipeKind, pos: (usize, usize)) -> Self {
+        Pipe { kind, pos }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Field {
+    pipes: Array2<Pipe>,
+}
+
+impl Field {
+    fn new(pipes: Array2<Pipe>) -> Self {
+        Field { pipes }
+    }
+
+    fn parse(input: &str) -> Result<Self> {
+        let nrow = input.lines().count();
+        ensure!(nrow > 0, "empty field");
+        let ncol = input.lines().next().unwrap().chars().count();
+        ensure!(
+            input.lines().all(|s| s.chars().count() == ncol),
+            "rows of different widths"
+        );
+        let flat_pipes: Vec<PipeKind> = input
+            .lines()
+            .flat_map(|s| s.chars())
+            .map(PipeKind::try_from)
+            .collect::<Result<_>>()?;
+        let pipes_kind = Array2::from_shape_vec((nrow, ncol), flat_pipes)?;
+        let pipes = Array2::from_shape_vec(
+            (nrow, ncol),
+            pipes_kind
+                .indexed_iter()
+                .map(|((i, j), p)| Pipe::new(*p, (i, j)))
+                .collect(),
+        )?;
+
+        Ok(Field::new(pipes))
+    }
+
+    fn find_start(&self) -> Option<&Pipe> {
+        self.pipes.iter().find(|p| matches!(p.kind, PipeKind::S))
+    }
+
+    fn get_neighbor(&self, p: &Pipe, dir: Direction) -> Option<&Pipe> {
+        let (i, j) = p.pos;
+        let (ni, nj) = match dir {
+            Direction::North => (i.checked_sub(1)?, j),
+            Direction::South => (i + 1, j),
+            Direction::East => (i, j + 1),
+            Direction::West => (i, j.checked_sub(1)?),
+        };
+        self.pipes.get((ni, nj))
+    }
+
+    fn get_next_direction(&self, neighbor: &Pipe, dir: Direction) -> Option<Direction> {
+        match neighbor.kind {
+            PipeKind::G => None,
+            PipeKind::S => None,
+            PipeKind::P([_, _]) => neighbor.kind.traverse(dir.opposite()),
+        }
+    }
+
+    fn get_next_step(&self, p: &Pipe, dir: Direction) -> Option<(&Pipe, Direction)> {
+        let neighbor = self.get_neighbor(p, dir)?;
+        let next_dir = self.get_next_direction(neighbor, dir)?;
+        Some((neighbor, next_dir))
+    }
+
+    fn startpos2startdir(&self, start: &Pipe) -> Option<(&Pipe, Direction)> {
+        let directions = [
+            Direction::North,
+            Direction::South,
+            Direction::East,
+            Direction::West,
+        ];
+        directions
+            .into_iter()
+            .find_map(|dir| self.get_next_step(start, dir))
+    }
+
+    fn follow_paths(&self, p: &Pipe, dir: Direction) -> Vec<Pipe> {
+        let mut p = p;
+        let mut nodes = vec![*p];
+        let mut dir = dir;
+        while let Some((next_p, next_dir)) = self.get_next_step(p, dir) {
+            p = next_p;
+            dir = next_dir;
+            nodes.push(*p);
+            trace::emit(|| {
+                let (row, col) = p.pos;
+                let step = nodes.len() - 1;
+                Event::new(
+                    "step",
+                    format!("step {} at ({},{}) heading {:?}", step, row, col, dir),
+                )
+                .field("step", step)
+                .field("row", row)
+                .field("col", col)
+                .field("heading", format!("{:?}", dir))
+            });
+        }
+        nodes
+    }
+
+    fn get_poly_path(&self, path: &[Pipe]) -> Polygon<f64> {
+        let coords: Vec<(f64, f64)> = path
+            .iter()
+            .map(|p| (p.pos.0 as f64, p.pos.1 as f64))
+            .collect();
+        Polygon::new(LineString::from(coords), vec![])
+    }
+
+    fn n_pipes_in_path(&self, path: &[Pipe]) -> usize {
+        let poly = self.get_poly_path(path);
+        par_iter!(self.pipes)
+            .filter(|p| !path.contains(p))
+            .map(|p| (p.pos.0 as f64, p.pos.1 as f64))
+            .filter(|p| poly.contains(&Point::new(p.0, p.1)))
+            .count()
+    }
+
+    /// Same as `n_pipes_in_path`, flipping between outside and inside each
+    /// time a row crosses a pipe of the loop going north.
+    fn n_pipes_in_path_parity(&self, path: &[Pipe]) -> usize {
+        let on_path: HashSet<(usize, usize)> = path.iter().map(|p| p.pos).collect();
+        let goes_north = |p: &Pipe| match p.kind {
+            PipeKind::P(dirs) => dirs.contains(&Direction::North),
+            PipeKind::S => self.get_next_step(p, Direction::North).is_some(),
+            PipeKind::G => false,
+        };
+        let mut n_inside = 0;
+        for row in self.pipes.rows() {
+            let mut inside = false;
+            for p in row {
+                if on_path.contains(&p.pos) {
+                    inside ^= goes_north(p);
+                } else if inside {
+                    n_inside += 1;
+                }
+            }
+        }
+        n_inside
+    }
+}
+
+/// The start and the first step of the path leaving it.
+fn start(field: &Field) -> Result<(&Pipe, &Pipe, Direction)> {
+    let start_pos = field.find_start().context("no start")?;
+    let (p, dir) = field
+        .startpos2startdir(start_pos)
+        .context("no pipe leaves the start")?;
+    Ok((start_pos, p, dir))
+}
+
+fn part_1(field: &Field) -> Result<usize> {
+    let (_, p, dir) = start(field)?;
+    Ok(field.follow_paths(p, dir).len() / 2 + 1)
+}
+
+fn loop_path(field: &Field) -> Result<Vec<Pipe>> {
+    let (start_pos, p, dir) = start(field)?;
+    let mut path = vec![*start_pos];
+    path.extend(field.follow_paths(p, dir));
+    Ok(path)
+}
+
+fn part_2(field: &Field) -> Result<usize> {
+    Ok(field.n_pipes_in_path(&loop_path(field)?))
+}
+
+fn part_2_parity(field: &Field) -> Result<usize> {
+    Ok(field.n_pipes_in_path_parity(&loop_path(field)?))
+}
+
+pub struct Day10;
+
+impl aoc::Solution for Day10 {
+    const DAY: u8 = 10;
+    type Model = Field;
+
+    fn parse(input: &str) -> Result<Self::Model> {
+        Field::parse(input)
+    }
+
+    fn part1(field: &Self::Model) -> Result<Answer> {
+        Ok(part_1(field)?.into())
+    }
+
+    fn part2(field: &Self::Model) -> Result<Answer> {
+        Ok(part_2(field)?.into())
+    }
+}
+
+impl Differential for Day10 {
+    fn alternatives(part: Part) -> Vec<Implementation<Self::Model>> {
+        match part {
+            Part::One => vec![],
+            Part::Two => vec![Implementation::new("parity", |field| {
+                Ok(part_2_parity(field)?.into())
+            })],
+        }
+    }
+
+    /// The loop is the outline of a blob of cells grown at random, kept free
+    /// of holes and of cells touching only by a corner; every other tile is
+    /// junk.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let n = size.min(12) + 3;
+        let mut cells = Array2::from_elem((n, n), false);
+        cells[(n / 2, n / 2)] = true;
+        for _ in 0..4 * size {
+            let cell = (
+                rng.range(1..n as u64 - 1) as usize,
+                rng.range(1..n as u64 - 1) as usize,
+            );
+            let (i, j) = cell;
+            if cells[cell]
+                || ![(i - 1, j), (i + 1, j), (i, j - 1), (i, j + 1)]
+                    .iter()
+                    .any(|&c| cells[c])
+            {
+                continue;
+            }
+            cells[cell] = true;
+            if has_hole(&cells) || has_pinch(&cells) {
+                cells[cell] = false;
+            }
+        }
+
+        let filled = |i: usize, j: usize| {
+            i.checked_sub(1)
+                .zip(j.checked_sub(1))
+                .and_then(|(i, j)| cells.get((i, j)))
+                .copied()
+                .unwrap_or(false)
+        };
+        // Vertex (i, j) sits between cells (i - 1, j - 1) and (i, j).
+        let mut tiles = Array2::from_shape_fn((n + 1, n + 1), |(i, j)| {
+            let north = filled(i, j) != filled(i, j + 1);
+            let south = filled(i + 1, j) != filled(i + 1, j + 1);
+            let west = filled(i, j) != filled(i + 1, j);
+            let east = filled(i, j + 1) != filled(i + 1, j + 1);
+            match (north, south, east, west) {
+                (true, true, false, false) => '|',
+                (false, false, true, true) => '-',
+                (true, false, true, false) => 'L',
+                (true, false, false, true) => 'J',
+                (false, true, false, true) => '7',
+                (false, true, true, false) => 'F',
+                _ => ' ',
+            }
+        });
+        let on_loop = tiles
+            .indexed_iter()
+            .filter(|(_, &c)| c != ' ')
+            .map(|(pos, _)| pos)
+            .collect_vec();
+        let (si, sj) = *rng.choose(&on_loop);
+        tiles[(si, sj)] = 'S';
+        for tile in tiles.iter_mut().filter(|c| **c == ' ') {
+            *tile = *rng.choose(&['.', '.', '.', '|', '-', 'L', 'J', '7', 'F']);
+        }
+        // Junk must not connect to the start.
+        for (i, j) in [
+            (si.wrapping_sub(1), sj),
+            (si + 1, sj),
+            (si, sj.wrapping_sub(1)),
+            (si, sj + 1),
+        ] {
+            if let Some(tile) = tiles.get_mut((i, j)) {
+                if !on_loop.contains(&(i, j)) {
+                    *tile = '.';
+                }
+            }
+        }
+
+        tiles
+            .rows()
+            .into_iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    /// The start connects to exactly two pipes, and the path leaving it
+    /// comes back to it.
+    fn is_valid(field: &Self::Model) -> bool {
+        let directions = [
+            Direction::North,
+            Direction::South,
+            Direction::East,
+            Direction::West,
+        ];
+        let Some(start) = field.find_start() else {
+            return false;
+        };
+        if directions
+            .iter()
+            .filter(|&&dir| field.get_next_step(start, dir).is_some())
+            .count()
+            != 2
+        {
+            return false;
+        }
+        let Some((p, dir)) = field.startpos2startdir(start) else {
+            return false;
+        };
+        let path = field.follow_paths(p, dir);
+        let last = path.last().unwrap();
+        path.len() >= 3
+            && matches!(last.kind, PipeKind::P(dirs)
+                if dirs.iter().any(|&d| field.get_neighbor(last, d) == Some(start)))
+    }
+
+    /// Drop a row or a column, or blank a tile.
+    fn shrink(input: &str) -> Vec<String> {
+        let rows = input
+            .lines()
+            .map(|row| row.chars().collect_vec())
+            .collect_vec();
+        let render = |rows: Vec<Vec<char>>| {
+            rows.into_iter()
+                .map(|row| row.into_iter().collect::<String>() + "\n")
+                .collect::<String>()
+        };
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut candidates = Vec::new();
+        for i in 0..rows.len() {
+            let mut fewer = rows.clone();
+            fewer.remove(i);
+            candidates.push(render(fewer));
+        }
+        for j in 0..width {
+            let narrower = rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|&(k, _)| k != j)
+                        .map(|(_, &c)| c)
+                        .collect()
+                })
+                .collect();
+            candidates.push(render(narrower));
+        }
+        for (i, row) in rows.iter().enumerate() {
+            for (j, &c) in row.iter().enumerate() {
+                if c != '.' && c != 'S' {
+                    let mut blanked = rows.clone();
+                    blanked[i][j] = '.';
+                    candidates.push(render(blanked));
+                }
+            }
+        }
+        candidates
+    }
+}
+
+impl aoc::fuzz::Fuzz for Day10 {
+    fn exercise(field: &Self::Model) {
+        let _ = part_1(field);
+        let _ = part_2(field);
+    }
+}
+
+impl Explore for Day10 {
+    fn commands() -> Vec<Command> {
+        vec![
+            Command::new("start", "", "show where the animal starts"),
+            Command::new("tile", "<row> <col>", "show a tile"),
+            Command::new(
+                "neighbors",
+                "<row> <col>",
+                "show the tiles around a tile, and which ones its pipe leads to",
+            ),
+        ]
+    }
+
+    fn query(field: &Self::Model, command: &str, args: &[&str]) -> Result<String> {
+        let describe = |p: &Pipe| format!("{} at {} {}", p.kind.symbol(), p.pos.0, p.pos.1);
+        if command == "start" {
+            let start = field.find_start().context("no start")?;
+            return Ok(describe(start));
+        }
+        let row = explore::arg(args, 0, "row")?;
+        let col = explore::arg(args, 1, "col")?;
+        let pipe = field
+            .pipes
+            .get((row, col))
+            .with_context(|| format!("no tile at {} {}", row, col))?;
+        match command {
+            "tile" => Ok(describe(pipe)),
+            "neighbors" => Ok([
+                Direction::North,
+                Direction::South,
+                Direction::East,
+                Direction::West,
+            ]
+            .into_iter()
+            .map(|dir| match field.get_neighbor(pipe, dir) {
+                None => format!("{:?}: edge", dir),
+                Some(neighbor) => {
+                    let connected = (pipe.kind == PipeKind::S || pipe.kind.traverse(dir).is_some())
+                        && field.get_next_direction(neighbor, dir).is_some();
+                    let link = if connected { " (connected)" } else { "" };
+                    format!("{:?}: {}{}", dir, describe(neighbor), link)
+                }
+            })
+            .join("\n")),
+            _ => bail!("unknown command {:?}", command),
+        }
+    }
+}
+
+fn has_pinch(cells: &Array2<bool>) -> bool {
+    cells
+        .windows((2, 2))
+        .into_iter()
+        .any(|w| w[(0, 0)] == w[(1, 1)] && w[(0, 1)] == w[(1, 0)] && w[(0, 0)] != w[(0, 1)])
+}
+
+fn has_hole(cells: &Array2<bool>) -> bool {
+    let (nrow, ncol) = cells.dim();
+    let mut outside = Array2::from_elem((nrow, ncol), false);
+    let mut stack = vec![(0, 0)];
+    while let Some((i, j)) = stack.pop() {
+        if cells[(i, j)] || outside[(i, j)] {
+            continue;
+        }
+        outside[(i, j)] = true;
+        if i > 0 {
+            stack.push((i - 1, j));
+        }
+        if i + 1 < nrow {
+            stack.push((i + 1, j));
+        }
+        if j > 0 {
+            stack.push((i, j - 1));
+        }
+        if j + 1 < ncol {
+            stack.push((i, j + 1));
+        }
+    }
+    cells
+        .iter()
+        .zip(outside.iter())
+        .any(|(&cell, &outside)| !cell && !outside)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_p1_1() {
+        let input = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
+        let field = Field::parse(input).unwrap();
+        assert_eq!(4, part_1(&field).unwrap());
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
+        let field = Field::parse(input).unwrap();
+        let mut events = Vec::new();
+        trace::traced(&mut |e: &Event| events.push(e.clone()), || {
+            part_1(&field).unwrap()
+        });
+        // The path stops before coming back to the start.
+        assert_eq!(events.len(), 6);
+        assert_eq!(events[0].kind, "step");
+        assert_eq!(events[5].fields[0], ("step", trace::Value::Int(6)));
+    }
+
+    #[test]
+    fn test_explore() {
+        let input = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
+        let field = Field::parse(input).unwrap();
+        assert_eq!(Day10::query(&field, "start", &[]).unwrap(), "S at 1 1");
+        assert_eq!(
+            Day10::query(&field, "neighbors", &["1", "1"]).unwrap(),
+            "North: . at 0 1\n\
+             South: | at 2 1 (connected)\n\
+             East: - at 1 2 (connected)\n\
+             West: . at 1 0"
+        );
+        assert_eq!(
+            Day10::query(&field, "neighbors", &["0", "3"])
+                .unwrap()
+                .lines()
+                .next(),
+            Some("North: edge")
+        );
+        assert_eq!(
+            Day10::query(&field, "tile", &["3", "3"]).unwrap(),
+            "J at 3 3"
+        );
+        assert!(Day10::query(&field, "tile", &["9", "9"]).is_err());
+    }
+
+    #[test]
+    fn test_p1_2() {
+        let input = "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...";
+        let field = Field::parse(input).unwrap();
+        assert_eq!(8, part_1(&field).unwrap());
+    }
+
+    #[test]
+    fn test_p2_1() {
+        let input = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+        let field = Field::parse(input).unwrap();
+        assert_eq!(4, part_2(&field).unwrap());
+    }
+
+    #[test]
+    fn test_p2_2() {
+        let input = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+        let field = Field::parse(input).unwrap();
+        assert_eq!(4, part_2(&field).unwrap());
+    }
+
+    #[test]
+    fn test_p2_3() {
+        let input = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+        let field = Field::parse(input).unwrap();
+        assert_eq!(10, part_2(&field).unwrap());
+    }
+
+    #[test]
+    fn test_p2_start_on_inner_corner() {
+        let input = ".......
+.F---7.
+.|...|.
+.|.S-J.
+.|.|...
+.L-J...
+.......";
+        let field = Field::parse(input).unwrap();
+        assert_eq!(5, part_2(&field).unwrap());
+        assert_eq!(5, part_2_parity(&field).unwrap());
+    }
+
+    #[test]
+    fn test_differential() {
+        aoc::diff::assert_agree::<Day10>(&Default::default());
+    }
+
+    #[test]
+    fn test_examples() {
+        aoc::spec::assert_examples::<Day10>();
+    }
+
+    #[test]
+    fn test_fuzz_regressions() {
+        aoc::fuzz::assert_regressions::<Day10>();
+    }
+}
@@ -0,0 +1,50 @@
+//! The days with a solver, and what else each of them supports, shared by the
+//! runner and the Python bindings. `aoc new` registers new days here.
+
+use anyhow::{anyhow, Result};
+use aoc::Day;
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::new::<day_01::Day01>()
+            .streamable::<day_01::Day01>()
+            .dumpable::<day_01::Day01>(),
+        Day::new::<day_02::Day02>()
+            .anonymizable::<day_02::Day02>()
+            .streamable::<day_02::Day02>()
+            .dumpable::<day_02::Day02>(),
+        Day::new::<day_03::Day03>().dumpable::<day_03::Day03>(),
+        Day::new::<day_04::Day04>()
+            .numeric::<day_04::Day04>()
+            .streamable::<day_04::Day04>()
+            .dumpable::<day_04::Day04>(),
+        Day::differential::<day_05::Day05>()
+            .explorable::<day_05::Day05>()
+            .anonymizable::<day_05::Day05>()
+            .numeric::<day_05::Day05>()
+            .dumpable::<day_05::Day05>(),
+        Day::differential::<day_06::Day06>().dumpable::<day_06::Day06>(),
+        Day::new::<day_07::Day07>()
+            .numeric::<day_07::Day07>()
+            .streamable::<day_07::Day07>()
+            .dumpable::<day_07::Day07>(),
+        Day::new::<day_08::Day08>()
+            .explorable::<day_08::Day08>()
+            .anonymizable::<day_08::Day08>()
+            .dumpable::<day_08::Day08>(),
+        Day::new::<day_09::Day09>()
+            .numeric::<day_09::Day09>()
+            .streamable::<day_09::Day09>()
+            .dumpable::<day_09::Day09>(),
+        Day::differential::<day_10::Day10>()
+            .explorable::<day_10::Day10>()
+            .dumpable::<day_10::Day10>(),
+    ]
+}
+
+pub fn find(day: u8) -> Result<Day> {
+    days()
+        .into_iter()
+        .find(|d| d.day == day)
+        .ok_or_else(|| anyhow!("day {} is not implemented", day))
+}
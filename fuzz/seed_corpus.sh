@@ -0,0 +1,17 @@
+#!/bin/sh
+# Seed the corpus of each fuzz target with its day's example inputs and its
+# regressions.
+set -e
+cd "$(dirname "$0")"
+for target in fuzz_targets/day_*.rs; do
+    day=$(basename "$target" .rs)
+    mkdir -p "corpus/$day"
+    for example in ../day-"${day#day_}"/data/test_*.txt; do
+        if [ -s "$example" ]; then
+            cp "$example" "corpus/$day/"
+        fi
+    done
+    if [ -d "regressions/$day" ]; then
+        cp regressions/"$day"/* "corpus/$day/"
+    fi
+done
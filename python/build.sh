@@ -0,0 +1,8 @@
+#!/bin/sh
+# Build the aoc2023 extension module next to this script, without maturin:
+#
+#     python/build.sh && PYTHONPATH=python python3 -c 'import aoc2023'
+set -e
+cd "$(dirname "$0")"
+cargo build --release --offline
+cp target/release/libaoc2023.so aoc2023.so
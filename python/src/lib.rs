@@ -0,0 +1,200 @@
+//! Python bindings for the solvers, built as the `aoc2023` extension module.
+//!
+//! ```python
+//! import aoc2023
+//!
+//! model = aoc2023.parse(5, open("day-05/data/test_input.txt").read())
+//! model.part1()                      # 35
+//! model.query("map", "seed", "79")   # 'seed 79 -> soil 81 -> … -> location 82'
+//!
+//! almanach = aoc2023.day05.Almanach(text)
+//! almanach.get_dst(79)               # 82
+//! aoc2023.day07.hand_type("QQQJA", jokers=True)  # 'FourKind'
+//! aoc2023.day09.extrapolate_pred([0, 3, 6, 9])   # 12
+//! ```
+
+use aoc::{Answer, Day, Part, Solution};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyInt;
+
+fn find(day: u8) -> PyResult<Day> {
+    days::find(day).map_err(error)
+}
+
+fn error(error: anyhow::Error) -> PyErr {
+    PyValueError::new_err(format!("{:#}", error))
+}
+
+fn part(part: u8) -> PyResult<Part> {
+    part.to_string().parse().map_err(PyValueError::new_err)
+}
+
+/// Integers become Python ints, whatever their size; text and grids become
+/// strings.
+fn answer(py: Python<'_>, answer: Answer) -> PyResult<PyObject> {
+    Ok(match answer {
+        Answer::Int(n) => n.into_pyobject(py)?.into_any().unbind(),
+        Answer::Big(n) => py.get_type::<PyInt>().call1((n.to_string(),))?.unbind(),
+        answer => answer.to_string().into_pyobject(py)?.into_any().unbind(),
+    })
+}
+
+/// The parsed input of a day.
+#[pyclass(unsendable, module = "aoc2023")]
+struct Model {
+    day: Day,
+    model: aoc::Model,
+}
+
+#[pymethods]
+impl Model {
+    #[getter]
+    fn day(&self) -> u8 {
+        self.day.day
+    }
+
+    fn solve(&self, py: Python<'_>, part: u8) -> PyResult<PyObject> {
+        let result = self.day.solve(&self.model, self::part(part)?);
+        answer(py, result.map_err(error)?)
+    }
+
+    fn part1(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.solve(py, 1)
+    }
+
+    fn part2(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.solve(py, 2)
+    }
+
+    /// The names of the queries of the day, as in `aoc repl`.
+    fn commands(&self) -> Vec<&'static str> {
+        self.day.commands().iter().map(|c| c.name).collect()
+    }
+
+    #[pyo3(signature = (command, *args))]
+    fn query(&self, command: &str, args: Vec<String>) -> PyResult<String> {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.day.query(&self.model, command, &args).map_err(error)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<aoc2023.Model of day {:02}>", self.day.day)
+    }
+}
+
+/// The days with a solver.
+#[pyfunction]
+fn implemented() -> Vec<u32> {
+    // As a list: a `Vec<u8>` would become `bytes`.
+    days::days().iter().map(|d| u32::from(d.day)).collect()
+}
+
+#[pyfunction]
+fn parse(day: u8, input: &str) -> PyResult<Model> {
+    let day = find(day)?;
+    let model = day.parse(input).map_err(error)?;
+    Ok(Model { day, model })
+}
+
+#[pyfunction]
+fn solve(py: Python<'_>, day: u8, input: &str, part: u8) -> PyResult<PyObject> {
+    parse(day, input)?.solve(py, part)
+}
+
+mod day05 {
+    use super::*;
+
+    #[pyclass(frozen, module = "aoc2023.day05")]
+    pub struct Almanach(pub day_05::Almanach);
+
+    #[pymethods]
+    impl Almanach {
+        #[new]
+        fn new(input: &str) -> PyResult<Self> {
+            day_05::Day05::parse(input).map(Almanach).map_err(error)
+        }
+
+        fn get_dst(&self, src: i64) -> i64 {
+            self.0.get_dst(&src)
+        }
+
+        fn get_src(&self, dst: i64) -> i64 {
+            self.0.get_src(&dst)
+        }
+
+        fn part1(&self, py: Python<'_>) -> PyResult<PyObject> {
+            answer(py, day_05::Day05::part1(&self.0).map_err(error)?)
+        }
+
+        fn part2(&self, py: Python<'_>) -> PyResult<PyObject> {
+            answer(py, day_05::Day05::part2(&self.0).map_err(error)?)
+        }
+    }
+}
+
+mod day07 {
+    use super::*;
+
+    /// The type of a hand such as `"32T3K"`, e.g. `"Pair"`.
+    #[pyfunction]
+    #[pyo3(signature = (cards, jokers = false))]
+    pub fn hand_type(cards: &str, jokers: bool) -> PyResult<String> {
+        let hand_type = day_07::HandType::of(cards, jokers).map_err(error)?;
+        Ok(format!("{:?}", hand_type))
+    }
+}
+
+mod day09 {
+    use super::*;
+
+    fn history(history: Vec<i64>) -> PyResult<Vec<i64>> {
+        if history.is_empty() {
+            return Err(PyValueError::new_err("empty history"));
+        }
+        Ok(history)
+    }
+
+    #[pyfunction]
+    pub fn extrapolate_pred(history: Vec<i64>) -> PyResult<i64> {
+        Ok(day_09::extrapolate_pred(self::history(history)?))
+    }
+
+    #[pyfunction]
+    pub fn extrapolate_pred_backward(history: Vec<i64>) -> PyResult<i64> {
+        Ok(day_09::extrapolate_pred_backward(self::history(history)?))
+    }
+}
+
+fn submodule<'py>(
+    parent: &Bound<'py, PyModule>,
+    name: &str,
+    init: impl FnOnce(&Bound<'py, PyModule>) -> PyResult<()>,
+) -> PyResult<()> {
+    let module = PyModule::new(parent.py(), name)?;
+    init(&module)?;
+    parent.add_submodule(&module)?;
+    // Make `import aoc2023.dayNN` work too.
+    parent
+        .py()
+        .import("sys")?
+        .getattr("modules")?
+        .set_item(format!("aoc2023.{}", name), &module)
+}
+
+#[pymodule]
+fn aoc2023(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Model>()?;
+    m.add_function(wrap_pyfunction!(implemented, m)?)?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    submodule(m, "day05", |m| m.add_class::<day05::Almanach>())?;
+    submodule(m, "day07", |m| {
+        m.add_function(wrap_pyfunction!(day07::hand_type, m)?)
+    })?;
+    submodule(m, "day09", |m| {
+        m.add_function(wrap_pyfunction!(day09::extrapolate_pred, m)?)?;
+        m.add_function(wrap_pyfunction!(day09::extrapolate_pred_backward, m)?)
+    })?;
+    Ok(())
+}
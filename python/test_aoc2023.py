@@ -0,0 +1,60 @@
+"""Checks of the aoc2023 module on the examples: python/build.sh first, then
+
+    PYTHONPATH=python python3 -m unittest discover python
+"""
+
+import pathlib
+import unittest
+
+import aoc2023
+from aoc2023 import day05, day07, day09
+
+ROOT = pathlib.Path(__file__).resolve().parent.parent
+
+
+def example(day, name="test_input.txt"):
+    return (ROOT / f"day-{day:02}" / "data" / name).read_text()
+
+
+class TestModel(unittest.TestCase):
+    def test_solve(self):
+        model = aoc2023.parse(4, example(4))
+        self.assertEqual(model.day, 4)
+        self.assertEqual((model.part1(), model.part2()), (13, 30))
+        self.assertEqual(aoc2023.solve(4, example(4), 2), 30)
+        self.assertIn(10, aoc2023.implemented())
+
+    def test_query(self):
+        model = aoc2023.parse(8, example(8, "test_input_1.txt"))
+        self.assertEqual(model.query("next", "AAA", "L"), "BBB")
+        self.assertIn("node", model.commands())
+
+    def test_errors(self):
+        with self.assertRaises(ValueError):
+            aoc2023.parse(11, "")
+        with self.assertRaises(ValueError):
+            aoc2023.parse(4, example(4)).solve(3)
+
+
+class TestDays(unittest.TestCase):
+    def test_almanach(self):
+        almanach = day05.Almanach(example(5))
+        self.assertEqual(almanach.get_dst(79), 82)
+        self.assertEqual(almanach.get_src(82), 79)
+        self.assertEqual(almanach.part1(), 35)
+
+    def test_hand_type(self):
+        self.assertEqual(day07.hand_type("32T3K"), "Pair")
+        self.assertEqual(day07.hand_type("QQQJA", jokers=True), "FourKind")
+        with self.assertRaises(ValueError):
+            day07.hand_type("QQQ")
+
+    def test_extrapolate(self):
+        self.assertEqual(day09.extrapolate_pred([0, 3, 6, 9, 12, 15]), 18)
+        self.assertEqual(day09.extrapolate_pred_backward([10, 13, 16, 21, 30, 45]), 5)
+        with self.assertRaises(ValueError):
+            day09.extrapolate_pred([])
+
+
+if __name__ == "__main__":
+    unittest.main()
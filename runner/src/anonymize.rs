@@ -0,0 +1,138 @@
+//! Inputs that can be shared, as written by `aoc anonymize`.
+//!
+//! A day's anonymizer rewrites its input, and both inputs are then solved to
+//! check that the answers to the new one follow from the original ones.
+
+use std::fmt;
+
+use anyhow::{bail, Result};
+use aoc::anonymize::Anonymized;
+use aoc::{diff, Answer, Day, Part};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The original input has no answer to compare with.
+    Skipped(String),
+    Checked {
+        expected: Answer,
+        answer: Result<Answer, String>,
+    },
+}
+
+/// How a part of the anonymized input compares to the original.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+impl Check {
+    pub fn is_ok(&self) -> bool {
+        match &self.outcome {
+            Outcome::Skipped(_) => true,
+            Outcome::Checked { expected, answer } => answer.as_ref() == Ok(expected),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "part {}: ", self.part)?;
+        match &self.outcome {
+            Outcome::Skipped(error) => write!(f, "skipped, the original fails: {}", error),
+            Outcome::Checked { expected, answer } => match answer {
+                Ok(answer) if answer == expected => write!(f, "{} ok", answer),
+                Ok(answer) => write!(f, "{} WRONG, expected {}", answer, expected),
+                Err(error) => write!(f, "{} WRONG, expected {}", error, expected),
+            },
+        }
+    }
+}
+
+/// The answer of `part`, or why there is none, panics included.
+fn answer(day: &Day, model: &aoc::Model, part: Part) -> Result<Answer, String> {
+    diff::catch(|| day.solve(model, part))
+        .map_err(|message| format!("panicked: {}", message))?
+        .map_err(|error| format!("{:#}", error))
+}
+
+/// Solve `parts` of `day` on `input` and on its anonymized version.
+pub fn verify(
+    day: &Day,
+    input: &str,
+    anonymized: &Anonymized,
+    parts: &[Part],
+) -> Result<Vec<Check>> {
+    let parse = |input: &str| match diff::catch(|| day.parse(input)) {
+        Ok(model) => model,
+        Err(message) => Err(anyhow::anyhow!("panicked: {}", message)),
+    };
+    let original = match parse(input) {
+        Ok(model) => model,
+        Err(error) => bail!("failed to parse the original input: {:#}", error),
+    };
+    let model = match parse(&anonymized.input) {
+        Ok(model) => model,
+        Err(error) => bail!("failed to parse the anonymized input: {:#}", error),
+    };
+    Ok(parts
+        .iter()
+        .map(|&part| {
+            let outcome = match answer(day, &original, part) {
+                Ok(original) => Outcome::Checked {
+                    expected: anonymized.expected(&original),
+                    answer: answer(day, &model, part),
+                },
+                Err(error) => Outcome::Skipped(error),
+            };
+            Check { part, outcome }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc::input;
+
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_verify() {
+        let examples = [
+            (2, "test_record.txt"),
+            (5, "test_input.txt"),
+            (8, "test_input_2.txt"),
+        ];
+        for (number, example) in examples {
+            let day = registry::find(number).unwrap();
+            let path = Path::new("..").join(input::data_dir(number)).join(example);
+            let input = input::read(path).unwrap();
+            let anonymized = day.anonymize(&input, 7).unwrap();
+            assert_ne!(anonymized.input, input);
+            let checks = verify(&day, &input, &anonymized, &Part::ALL).unwrap();
+            assert!(
+                checks.iter().all(Check::is_ok),
+                "day {}: {:?}",
+                number,
+                checks
+            );
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let day = registry::find(8).unwrap();
+        let input = input::read("../day-08/data/test_input_2.txt").unwrap();
+        let mut anonymized = day.anonymize(&input, 1).unwrap();
+        anonymized.shift = 1;
+        let checks = verify(&day, &input, &anonymized, &Part::ALL).unwrap();
+        assert!(!checks[0].is_ok());
+        assert_eq!(checks[0].to_string(), "part 1: 6 WRONG, expected 7");
+        assert!(!checks[1].is_ok());
+
+        assert!(registry::find(1).unwrap().anonymize(&input, 1).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Solve a day on a directory of inputs, e.g. those of several people.
+//!
+//! Each input `NAME.txt` may come with `NAME.expected`, holding one
+//! `part P: answer` line per known answer, which the computed answers are
+//! checked against. Inputs may be compressed, e.g. `NAME.txt.gz`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use aoc::{input, Answer, Day, Part};
+use serde::Serialize;
+
+use crate::memory;
+use crate::report::DayReport;
+
+const EXPECTED: &str = "expected";
+
+/// The inputs of `dir`, that is its files but the expected answers.
+pub fn inputs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = fs::read_dir(dir).with_context(|| format!("failed to list {}", dir.display()))?;
+    let mut inputs = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if path.is_file() && !hidden && path.extension().is_none_or(|e| e != EXPECTED) {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// The answers expected for `input`, if it has an expected file.
+pub fn expected(input: &Path) -> Result<BTreeMap<Part, Answer>> {
+    let path = input::uncompressed(input).with_extension(EXPECTED);
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    input::read(&path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let parsed = line
+                .trim()
+                .strip_prefix("part ")
+                .and_then(|line| line.split_once(':'))
+                .and_then(|(part, answer)| Some((part.parse().ok()?, answer.parse().ok()?)));
+            parsed.ok_or_else(|| {
+                anyhow!(
+                    "{}: expected part <1|2>: <answer>, got {:?}",
+                    path.display(),
+                    line
+                )
+            })
+        })
+        .collect()
+}
+
+/// An expected answer, and whether it was found.
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub part: u8,
+    pub expected: String,
+    pub ok: bool,
+}
+
+/// The report on one input, with its checks.
+#[derive(Debug, Clone, Serialize)]
+pub struct Outcome {
+    #[serde(flatten)]
+    pub report: DayReport,
+    pub checks: Vec<Check>,
+}
+
+impl Outcome {
+    fn new(day: &Day, path: &Path, parts: &[Part]) -> Self {
+        let mut report = DayReport::run(day, path, parts);
+        let expected = match expected(path) {
+            Ok(expected) => expected,
+            Err(error) => {
+                report.parse.error.get_or_insert(format!("{:#}", error));
+                BTreeMap::new()
+            }
+        };
+        let checks = expected
+            .into_iter()
+            .filter(|(part, _)| parts.contains(part))
+            .map(|(part, expected)| {
+                let answer = report
+                    .parts
+                    .iter()
+                    .find(|p| p.part == part.number())
+                    .and_then(|p| p.answer.as_ref()?.parse::<Answer>().ok());
+                Check {
+                    part: part.number(),
+                    ok: answer.as_ref() == Some(&expected),
+                    expected: expected.to_string(),
+                }
+            })
+            .collect();
+        Outcome { report, checks }
+    }
+
+    /// Whether the input was solved without errors nor wrong answers.
+    pub fn is_ok(&self) -> bool {
+        self.report.is_ok() && self.checks.iter().all(|c| c.ok)
+    }
+}
+
+/// One line per part: the answer, how long it took, and whether it was right.
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = Path::new(&self.report.input.path);
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if let Some(error) = &self.report.parse.error {
+            return writeln!(f, "{}: {}", name, error);
+        }
+        for part in &self.report.parts {
+            write!(f, "{} part {}: ", name, part.part)?;
+            match (&part.answer, &part.error) {
+                (Some(answer), _) => write!(f, "{}", answer.replace('\n', " / "))?,
+                (None, error) => write!(f, "{}", error.as_deref().unwrap_or(""))?,
+            }
+            write!(f, " ({:?})", Duration::from_nanos(part.duration_ns))?;
+            match self.checks.iter().find(|c| c.part == part.part) {
+                Some(check) if check.ok => writeln!(f, " ok")?,
+                Some(check) => writeln!(f, " WRONG, expected {}", check.expected)?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Solve `parts` of `day` on every input of `dir`, on up to `jobs` threads.
+/// Outcomes are in the order of the inputs; memory is only measured on a
+/// single thread.
+pub fn batch(day: &Day, dir: &Path, parts: &[Part], jobs: usize) -> Result<Vec<Outcome>> {
+    let inputs = inputs(dir)?;
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new(BTreeMap::new());
+    let jobs = jobs.clamp(1, inputs.len().max(1));
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = inputs.get(i) else {
+                    break;
+                };
+                let outcome = match jobs {
+                    1 => Outcome::new(day, path, parts),
+                    _ => memory::unmeasured(|| Outcome::new(day, path, parts)),
+                };
+                outcomes.lock().unwrap().insert(i, outcome);
+            });
+        }
+    });
+    Ok(outcomes.into_inner().unwrap().into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_batch() {
+        let dir = tempfile::tempdir().unwrap();
+        let example = aoc::input::read("../day-04/data/test_input.txt").unwrap();
+        fs::write(dir.path().join("alice.txt"), &example).unwrap();
+        fs::write(
+            dir.path().join("alice.expected"),
+            "part 1: 13\npart 2: 30\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("bob.txt"), &example).unwrap();
+        fs::write(dir.path().join("bob.expected"), "part 2: 31\n").unwrap();
+        let carol = zstd::encode_all("Card 1: x | y\n".as_bytes(), 0).unwrap();
+        fs::write(dir.path().join("carol.txt.zst"), carol).unwrap();
+        fs::write(dir.path().join(".notes"), "").unwrap();
+
+        let day = registry::find(4).unwrap();
+        let outcomes = batch(&day, dir.path(), &Part::ALL, 2).unwrap();
+        assert_eq!(outcomes.len(), 3);
+
+        let [alice, bob, carol] = &outcomes[..] else {
+            unreachable!()
+        };
+        assert!(alice.is_ok());
+        assert_eq!(alice.checks.len(), 2);
+        assert!(alice.to_string().starts_with("alice.txt part 1: 13 ("));
+        assert!(!bob.is_ok());
+        assert!(bob.to_string().contains("WRONG, expected 31"));
+        assert!(!carol.is_ok());
+        let error = carol.report.parse.error.as_deref().unwrap();
+        assert!(error.starts_with("invalid number \"x\""), "{}", error);
+
+        let json: serde_json::Value = serde_json::to_value(bob).unwrap();
+        assert_eq!(json["day"], 4);
+        assert_eq!(json["checks"][0]["ok"], false);
+    }
+
+    #[test]
+    fn test_expected() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.txt");
+        assert!(expected(&input).unwrap().is_empty());
+        fs::write(
+            dir.path().join("input.expected"),
+            "part 2: 5\n\npart 1: abc\n",
+        )
+        .unwrap();
+        let expected = expected(&input).unwrap();
+        assert_eq!(expected[&Part::One], Answer::text("abc"));
+        assert_eq!(expected[&Part::Two], Answer::Int(5));
+
+        fs::write(dir.path().join("input.expected"), "5\n").unwrap();
+        assert!(super::expected(&input).is_err());
+        assert!(super::expected(&dir.path().join("input.txt.gz")).is_err());
+    }
+}
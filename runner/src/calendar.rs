@@ -0,0 +1,283 @@
+//! Every day of the calendar at once, as printed by `aoc run --all`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc::{input, Day, Part};
+use serde::Serialize;
+
+use crate::memory;
+use crate::report::DayReport;
+
+pub const DAYS: std::ops::RangeInclusive<u8> = 1..=25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    /// Every implemented part was answered.
+    Solved,
+    /// The input could not be parsed, or a part failed or panicked.
+    Failed,
+    TimedOut,
+    /// The day has no solver, or none of its parts is implemented.
+    Unimplemented,
+}
+
+impl Status {
+    fn of(report: &DayReport) -> Self {
+        if !report.is_ok() {
+            Status::Failed
+        } else if report.parts.iter().all(|p| p.answer.is_none()) {
+            Status::Unimplemented
+        } else {
+            Status::Solved
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            Status::Solved => "solved",
+            Status::Failed => "failed",
+            Status::TimedOut => "timed out",
+            Status::Unimplemented => "unimplemented",
+        };
+        f.pad(status)
+    }
+}
+
+/// How a day went; there is no report when it has no solver or timed out.
+#[derive(Debug, Clone, Serialize)]
+pub struct Entry {
+    pub day: u8,
+    pub status: Status,
+    pub report: Option<DayReport>,
+}
+
+impl Entry {
+    fn new(day: u8, report: Option<DayReport>, status: Status) -> Self {
+        Entry {
+            day,
+            status,
+            report,
+        }
+    }
+
+    /// The answer of a part or why there is none, on one line.
+    fn part(&self, part: Part) -> String {
+        let Some(report) = &self.report else {
+            return String::new();
+        };
+        if let Some(error) = &report.parse.error {
+            return if part == Part::One {
+                error.clone()
+            } else {
+                String::new()
+            };
+        }
+        let Some(part) = report.parts.iter().find(|p| p.part == part.number()) else {
+            return String::new();
+        };
+        match (&part.answer, &part.error) {
+            (Some(answer), _) => answer.replace('\n', " / "),
+            (None, error) => error.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Time spent parsing and solving, if the day finished.
+    pub fn duration(&self) -> Option<Duration> {
+        let report = self.report.as_ref()?;
+        let nanos =
+            report.parse.duration_ns + report.parts.iter().map(|p| p.duration_ns).sum::<u64>();
+        Some(Duration::from_nanos(nanos))
+    }
+}
+
+/// Solve `parts` of `days` on their inputs under `root`, each on its own
+/// thread, and list every day of the calendar. Days still running after
+/// `timeout` are left behind as timed out.
+pub fn run_all(days: &[Day], root: &Path, parts: &[Part], timeout: Duration) -> Vec<Entry> {
+    let deadline = Instant::now() + timeout;
+    let (sender, receiver) = mpsc::channel();
+    let mut running = 0;
+    for &day in days {
+        let path = root.join(input::data_dir(day.day)).join("input.txt");
+        let parts = parts.to_vec();
+        let sender = sender.clone();
+        let spawned = thread::Builder::new()
+            .name(format!("day-{:02}", day.day))
+            .spawn(move || {
+                // The receiver is gone if the day timed out.
+                let report = memory::unmeasured(|| DayReport::run(&day, &path, &parts));
+                let _ = sender.send(report);
+            });
+        if spawned.is_ok() {
+            running += 1;
+        }
+    }
+
+    let mut reports = BTreeMap::new();
+    while reports.len() < running {
+        let left = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(left) {
+            Ok(report) => {
+                reports.insert(report.day, report);
+            }
+            Err(_) => break,
+        }
+    }
+
+    DAYS.map(|day| match reports.remove(&day) {
+        Some(report) => {
+            let status = Status::of(&report);
+            Entry::new(day, Some(report), status)
+        }
+        None if days.iter().any(|d| d.day == day) => Entry::new(day, None, Status::TimedOut),
+        None => Entry::new(day, None, Status::Unimplemented),
+    })
+    .collect()
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut text: String = text.chars().take(width - 1).collect();
+    text.push('…');
+    text
+}
+
+/// One row per day, and a count of each status.
+pub fn table(entries: &[Entry]) -> String {
+    const WIDTH: usize = 24;
+    let mut table = format!(
+        "day  {:<13}  {:<WIDTH$}  {:<WIDTH$}  time\n",
+        "status", "part 1", "part 2"
+    );
+    for entry in entries {
+        let row = format!(
+            " {:02}  {:<13}  {:<WIDTH$}  {:<WIDTH$}  {}",
+            entry.day,
+            entry.status,
+            truncate(&entry.part(Part::One), WIDTH),
+            truncate(&entry.part(Part::Two), WIDTH),
+            entry
+                .duration()
+                .map_or(String::new(), |d| format!("{:.1?}", d)),
+        );
+        table.push_str(row.trim_end());
+        table.push('\n');
+    }
+    let statuses = [
+        Status::Solved,
+        Status::Failed,
+        Status::TimedOut,
+        Status::Unimplemented,
+    ];
+    let counts: Vec<String> = statuses
+        .iter()
+        .map(|status| {
+            let count = entries.iter().filter(|e| e.status == *status).count();
+            format!("{} {}", count, status)
+        })
+        .collect();
+    table.push_str(&counts.join(", "));
+    table.push('\n');
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Condvar, Mutex};
+
+    use aoc::{Solution, Unimplemented};
+
+    use super::*;
+    use crate::registry;
+
+    static RELEASED: (Mutex<bool>, Condvar) = (Mutex::new(false), Condvar::new());
+
+    /// A day 4 whose parsing waits until [`RELEASED`], so that it times out.
+    struct Blocked;
+
+    impl Solution for Blocked {
+        const DAY: u8 = 4;
+        type Model = ();
+
+        fn parse(_input: &str) -> anyhow::Result<()> {
+            let (released, condvar) = &RELEASED;
+            let _released = condvar
+                .wait_while(released.lock().unwrap(), |released| !*released)
+                .unwrap();
+            Ok(())
+        }
+    }
+
+    fn report(day: u8, file: &str) -> DayReport {
+        let path = Path::new("..").join(input::data_dir(day)).join(file);
+        DayReport::run(&registry::find(day).unwrap(), &path, &Part::ALL)
+    }
+
+    #[test]
+    fn test_status() {
+        assert_eq!(Status::of(&report(4, "test_input.txt")), Status::Solved);
+        assert_eq!(Status::of(&report(4, "missing.txt")), Status::Failed);
+        // Part 1 is not implemented, part 2 is.
+        assert_eq!(Status::of(&report(1, "test_input.txt")), Status::Solved);
+
+        let mut report = report(4, "test_input.txt");
+        for part in &mut report.parts {
+            part.answer = None;
+            part.error = Some(Unimplemented.to_string());
+        }
+        assert_eq!(Status::of(&report), Status::Unimplemented);
+        report.parts[0].error = Some("panicked: oops".to_string());
+        assert_eq!(Status::of(&report), Status::Failed);
+    }
+
+    #[test]
+    fn test_run_all() {
+        let days = [registry::find(4).unwrap(), registry::find(9).unwrap()];
+        let entries = run_all(&days, Path::new(".."), &Part::ALL, Duration::from_secs(60));
+        assert_eq!(entries.len(), 25);
+        assert_eq!(entries[3].status, Status::Solved);
+        assert_eq!(entries[8].status, Status::Solved);
+        assert_eq!(entries[8].part(Part::Two), "988");
+        assert!(entries
+            .iter()
+            .filter(|e| ![4, 9].contains(&e.day))
+            .all(|e| e.status == Status::Unimplemented && e.report.is_none()));
+
+        let blocked = [Day::new::<Blocked>()];
+        let timeout = Duration::from_millis(10);
+        let entries = run_all(&blocked, Path::new(".."), &Part::ALL, timeout);
+        assert_eq!(entries[3].status, Status::TimedOut);
+        assert!(entries[3].report.is_none());
+        let (released, condvar) = &RELEASED;
+        *released.lock().unwrap() = true;
+        condvar.notify_all();
+    }
+
+    #[test]
+    fn test_table() {
+        let entries = [
+            Entry::new(4, Some(report(4, "test_input.txt")), Status::Solved),
+            Entry::new(5, None, Status::TimedOut),
+            Entry::new(12, None, Status::Unimplemented),
+        ];
+        let table = table(&entries);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("day  status"));
+        assert!(lines[1].starts_with(" 04  solved         13  "));
+        assert_eq!(lines[2], " 05  timed out");
+        assert_eq!(lines[3], " 12  unimplemented");
+        assert_eq!(lines[4], "1 solved, 0 failed, 1 timed out, 1 unimplemented");
+        assert_eq!(truncate("abcdef", 4), "abc…");
+    }
+}
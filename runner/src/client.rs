@@ -0,0 +1,331 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use aoc::Part;
+
+const BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u16 = 2023;
+const USER_AGENT: &str = concat!("aoc-2023-runner/", env!("CARGO_PKG_VERSION"));
+
+/// Environment variable holding the `session` cookie of a logged in browser.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Client for the Advent of Code website, which throttles its own requests so
+/// that consecutive ones are at least `interval` apart.
+pub struct Client {
+    base: String,
+    session: String,
+    interval: Duration,
+    agent: ureq::Agent,
+    last: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(base: impl Into<String>, session: impl Into<String>) -> Self {
+        Client {
+            base: base.into(),
+            session: session.into(),
+            interval: Duration::from_secs(5),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            last: None,
+        }
+    }
+
+    /// A client for adventofcode.com, authenticated from [`SESSION_VAR`].
+    pub fn from_env() -> Result<Self> {
+        let session = env::var(SESSION_VAR)
+            .with_context(|| format!("{} must hold your session cookie", SESSION_VAR))?;
+        Ok(Client::new(BASE_URL, session.trim()))
+    }
+
+    fn url(&self, day: u8, page: &str) -> String {
+        format!("{}/{}/day/{}{}", self.base, YEAR, day, page)
+    }
+
+    fn throttle(&mut self) {
+        if let Some(last) = self.last {
+            let ready = last + self.interval;
+            let now = Instant::now();
+            if ready > now {
+                thread::sleep(ready - now);
+            }
+        }
+        self.last = Some(Instant::now());
+    }
+
+    fn request(&mut self, method: &str, url: &str) -> ureq::Request {
+        self.throttle();
+        self.agent
+            .request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    fn get(&mut self, url: &str) -> Result<String> {
+        response(url, self.request("GET", url).call())
+    }
+
+    fn post(&mut self, url: &str, form: &[(&str, &str)]) -> Result<String> {
+        response(url, self.request("POST", url).send_form(form))
+    }
+
+    pub fn input(&mut self, day: u8) -> Result<String> {
+        self.get(&self.url(day, "/input"))
+    }
+
+    /// The first example of the day's puzzle description.
+    pub fn example(&mut self, day: u8) -> Result<String> {
+        let page = self.get(&self.url(day, ""))?;
+        first_example(&page).ok_or_else(|| anyhow!("no example in the day {} puzzle", day))
+    }
+
+    /// Submit the answer of a part, returning the article of the response.
+    pub fn answer(&mut self, day: u8, part: Part, answer: &str) -> Result<String> {
+        let page = self.post(
+            &self.url(day, "/answer"),
+            &[("level", &part.to_string()), ("answer", answer)],
+        )?;
+        Ok(article(&page).unwrap_or(&page).to_string())
+    }
+
+    /// Download the input and example of `day` into `dir`, unless they are
+    /// already there. Returns the files written.
+    pub fn fetch(&mut self, day: u8, dir: &Path) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir)?;
+        let mut written = Vec::new();
+        for (file, download) in [
+            (
+                "input.txt",
+                Client::input as fn(&mut Self, u8) -> Result<String>,
+            ),
+            ("test_input.txt", Client::example),
+        ] {
+            let path = dir.join(file);
+            if is_cached(&path) {
+                continue;
+            }
+            let content = download(self, day)?;
+            fs::write(&path, content)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+fn is_cached(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+fn response(url: &str, result: Result<ureq::Response, ureq::Error>) -> Result<String> {
+    match result {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(429, response)) => {
+            let retry = response.header("Retry-After").unwrap_or("a while");
+            bail!("rate limited by {}, retry after {} seconds", url, retry)
+        }
+        Err(ureq::Error::Status(code @ (400 | 500), _)) => {
+            bail!("{} answered {}, is {} still valid?", url, code, SESSION_VAR)
+        }
+        Err(ureq::Error::Status(404, _)) => bail!("{} is not unlocked yet", url),
+        Err(error) => Err(error).with_context(|| format!("failed to fetch {}", url)),
+    }
+}
+
+fn article(page: &str) -> Option<&str> {
+    let start = page.find("<article>")? + "<article>".len();
+    let end = start + page[start..].find("</article>")?;
+    Some(&page[start..end])
+}
+
+/// The content of the first `<pre><code>` block of a puzzle page.
+fn first_example(page: &str) -> Option<String> {
+    let start = page.find("<pre><code>")? + "<pre><code>".len();
+    let end = start + page[start..].find("</code></pre>")?;
+    Some(unescape(&strip_tags(&page[start..end])))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    /// A request received by the [`MockServer`].
+    #[derive(Debug, Clone)]
+    pub struct Received {
+        pub method: String,
+        pub url: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    /// A local HTTP server answering every request with `respond`, and
+    /// recording what it received.
+    pub struct MockServer {
+        pub base: String,
+        pub received: Arc<Mutex<Vec<Received>>>,
+    }
+
+    impl MockServer {
+        pub fn start(respond: impl Fn(&Received) -> (u16, String) + Send + 'static) -> Self {
+            let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+            let base = format!("http://{}", server.server_addr().to_ip().unwrap());
+            let received = Arc::new(Mutex::new(Vec::new()));
+            let log = Arc::clone(&received);
+            thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    let mut body = String::new();
+                    request.as_reader().read_to_string(&mut body).unwrap();
+                    let cookie = request
+                        .headers()
+                        .iter()
+                        .find(|h| h.field.equiv("Cookie"))
+                        .map(|h| h.value.to_string());
+                    let received = Received {
+                        method: request.method().to_string(),
+                        url: request.url().to_string(),
+                        cookie,
+                        body,
+                    };
+                    let (status, content) = respond(&received);
+                    log.lock().unwrap().push(received);
+                    let response =
+                        tiny_http::Response::from_string(content).with_status_code(status);
+                    request.respond(response).unwrap();
+                }
+            });
+            MockServer { base, received }
+        }
+
+        pub fn client(&self) -> Client {
+            Client {
+                interval: Duration::ZERO,
+                ..Client::new(&self.base, "secret")
+            }
+        }
+
+        pub fn received(&self) -> Vec<Received> {
+            self.received.lock().unwrap().clone()
+        }
+    }
+
+    const PUZZLE: &str = "<article><p>For example:</p>\
+        <pre><code>Time:      7  15   30\n<em>Distance</em>:  9  40  200\n</code></pre>\
+        <p>Another:</p><pre><code>a &lt; b &amp;&amp; c\n</code></pre></article>";
+
+    fn aoc(request: &Received) -> (u16, String) {
+        match request.url.as_str() {
+            "/2023/day/6/input" => (200, "Time: 1\nDistance: 2\n".to_string()),
+            "/2023/day/6" => (200, PUZZLE.to_string()),
+            _ => (404, "not found".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_first_example() {
+        assert_eq!(
+            first_example(PUZZLE).unwrap(),
+            "Time:      7  15   30\nDistance:  9  40  200\n"
+        );
+        assert_eq!(unescape("a &lt; b &amp;&amp; c"), "a < b && c");
+        assert_eq!(first_example("<p>no example</p>"), None);
+    }
+
+    #[test]
+    fn test_fetch() {
+        let server = MockServer::start(aoc);
+        let dir = tempfile::tempdir().unwrap();
+        let written = server.client().fetch(6, dir.path()).unwrap();
+        assert_eq!(written.len(), 2);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("input.txt")).unwrap(),
+            "Time: 1\nDistance: 2\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("test_input.txt")).unwrap(),
+            "Time:      7  15   30\nDistance:  9  40  200\n"
+        );
+
+        let received = server.received();
+        assert_eq!(received.len(), 2);
+        assert!(received
+            .iter()
+            .all(|r| r.method == "GET" && r.cookie.as_deref() == Some("session=secret")));
+    }
+
+    #[test]
+    fn test_fetch_is_cached() {
+        let server = MockServer::start(aoc);
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("input.txt"), "mine").unwrap();
+        fs::write(dir.path().join("test_input.txt"), "").unwrap();
+
+        let mut client = server.client();
+        let written = client.fetch(6, dir.path()).unwrap();
+        assert_eq!(written, vec![dir.path().join("test_input.txt")]);
+        assert!(client.fetch(6, dir.path()).unwrap().is_empty());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("input.txt")).unwrap(),
+            "mine"
+        );
+        assert_eq!(server.received().len(), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let server = MockServer::start(|request| match request.url.as_str() {
+            "/2023/day/1/input" => (429, "slow down".to_string()),
+            "/2023/day/2/input" => (400, "log in".to_string()),
+            _ => (404, "not found".to_string()),
+        });
+        let mut client = server.client();
+        let error = client.input(1).unwrap_err().to_string();
+        assert!(error.contains("rate limited"), "{}", error);
+        let error = client.input(2).unwrap_err().to_string();
+        assert!(error.contains(SESSION_VAR), "{}", error);
+        let error = client.input(25).unwrap_err().to_string();
+        assert!(error.contains("not unlocked"), "{}", error);
+    }
+
+    #[test]
+    fn test_throttle() {
+        let server = MockServer::start(aoc);
+        let mut client = Client {
+            interval: Duration::from_millis(200),
+            ..server.client()
+        };
+        let start = Instant::now();
+        client.input(6).unwrap();
+        client.input(6).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
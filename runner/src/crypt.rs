@@ -0,0 +1,124 @@
+//! Encrypting the data directories at rest, as done by `aoc encrypt` and
+//! `aoc decrypt`; see `aoc::crypt` for the format and where keys come from.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use aoc::crypt::{self, Key};
+use aoc::input;
+
+/// The non-empty `.txt` files, compressed or not, of `day`'s data directory
+/// under `root`, or of every day's.
+pub fn data_files(root: &Path, day: Option<u8>) -> Result<Vec<PathBuf>> {
+    let dirs = match day {
+        Some(day) => vec![root.join(input::data_dir(day))],
+        None => {
+            let mut dirs = Vec::new();
+            for entry in
+                fs::read_dir(root).with_context(|| format!("failed to list {}", root.display()))?
+            {
+                let entry = entry?;
+                if entry.file_name().to_string_lossy().starts_with("day-") {
+                    dirs.push(entry.path().join("data"));
+                }
+            }
+            dirs
+        }
+    };
+    let mut files = Vec::new();
+    for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+        for entry in
+            fs::read_dir(dir).with_context(|| format!("failed to list {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if input::uncompressed(&path)
+                .extension()
+                .is_some_and(|e| e == "txt")
+                && path.metadata().is_ok_and(|m| m.len() > 0)
+            {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Rewrite each of `files` that `apply` changes, returning those.
+fn rewrite(
+    files: &[PathBuf],
+    apply: impl Fn(&[u8]) -> Result<Option<Vec<u8>>>,
+) -> Result<Vec<PathBuf>> {
+    let mut changed = Vec::new();
+    for path in files {
+        let data = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        let Some(data) =
+            apply(&data).with_context(|| format!("failed to convert {}", path.display()))?
+        else {
+            continue;
+        };
+        fs::write(path, data).with_context(|| format!("failed to write {}", path.display()))?;
+        changed.push(path.clone());
+    }
+    Ok(changed)
+}
+
+/// Encrypt those of `files` still in plaintext.
+pub fn encrypt(key: &Key, files: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    rewrite(files, |data| {
+        Ok((!crypt::is_encrypted(data)).then(|| key.encrypt(data)))
+    })
+}
+
+/// Decrypt those of `files` that are encrypted.
+pub fn decrypt(key: &Key, files: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    rewrite(files, |data| match crypt::is_encrypted(data) {
+        true => key.decrypt(data).map(Some),
+        false => Ok(None),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt() {
+        let root = tempfile::tempdir().unwrap();
+        for day in [4, 9] {
+            let data = root.path().join(input::data_dir(day));
+            fs::create_dir_all(&data).unwrap();
+            fs::write(data.join("input.txt"), format!("day {}", day)).unwrap();
+            fs::write(data.join("test_input.txt"), "").unwrap();
+            fs::write(data.join("attempts.tsv"), "").unwrap();
+        }
+        let big = root.path().join("day-09/data/big.txt.zst");
+        fs::write(&big, zstd::encode_all(&b"0 1 2\n"[..], 0).unwrap()).unwrap();
+        fs::create_dir_all(root.path().join("runner")).unwrap();
+
+        let files = data_files(root.path(), None).unwrap();
+        let input = root.path().join("day-04/data/input.txt");
+        assert_eq!(
+            files,
+            [
+                input.clone(),
+                big.clone(),
+                root.path().join("day-09/data/input.txt")
+            ]
+        );
+        assert_eq!(data_files(root.path(), Some(4)).unwrap(), &files[..1]);
+        assert!(data_files(root.path(), Some(5)).unwrap().is_empty());
+
+        let key = Key::generate();
+        assert_eq!(encrypt(&key, &files).unwrap(), files);
+        assert!(crypt::is_encrypted(&fs::read(&input).unwrap()));
+        assert!(encrypt(&key, &files).unwrap().is_empty());
+        assert!(decrypt(&Key::generate(), &files).is_err());
+
+        assert_eq!(decrypt(&key, &files[..1]).unwrap(), &files[..1]);
+        assert_eq!(fs::read_to_string(&input).unwrap(), "day 4");
+        assert_eq!(decrypt(&key, &files).unwrap(), &files[1..]);
+        assert_eq!(input::read(&big).unwrap(), "0 1 2\n");
+    }
+}
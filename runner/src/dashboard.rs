@@ -0,0 +1,350 @@
+//! A static HTML calendar of progress, as written by `aoc dashboard`.
+//!
+//! `index.html` shows the 25 days: whether they are solved, how many parts
+//! match the accepted answers of `data/attempts.tsv`, and how long they take.
+//! Each day run also gets `day-NN.html`, charting the time spent parsing and
+//! solving each of its inputs, examples included. Pages need no other file.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use aoc::{input, Answer, Day, Part};
+
+use crate::calendar::{self, Entry, Status};
+use crate::report::DayReport;
+use crate::submit::History;
+
+const STYLE: &str = "
+body { font-family: sans-serif; background: #0f0f23; color: #ccc; margin: 2em; }
+a { color: inherit; text-decoration: none; }
+h1, h2 { color: #fff; }
+.calendar { display: grid; grid-template-columns: repeat(5, 9em); gap: 0.5em; }
+.day { border: 1px solid #333; padding: 0.5em; height: 5em; }
+.day .number { font-size: 1.5em; color: #fff; }
+.solved { border-color: #090; }
+.failed, .timed-out { border-color: #c00; }
+.unimplemented { opacity: 0.4; }
+.stars { color: #ff0; letter-spacing: 0.2em; }
+.time, .status { font-size: 0.8em; }
+table { border-collapse: collapse; margin: 1em 0; }
+td, th { border: 1px solid #333; padding: 0.3em 0.8em; text-align: left; }
+.ok { color: #0c0; }
+.wrong { color: #f44; }
+svg text { fill: #ccc; font-size: 12px; }
+";
+
+/// Everything shown about a day.
+pub struct DayResults {
+    pub entry: Entry,
+    /// Reports on the examples of `data`, in file order.
+    pub examples: Vec<DayReport>,
+    /// The answers accepted by the website.
+    pub accepted: BTreeMap<Part, Answer>,
+}
+
+impl DayResults {
+    /// Whether the answer to the real input of `part` was accepted; `None` if
+    /// no answer was accepted yet.
+    pub fn verified(&self, part: Part) -> Option<bool> {
+        let accepted = self.accepted.get(&part)?;
+        let answer = self
+            .entry
+            .report
+            .as_ref()?
+            .parts
+            .iter()
+            .find(|p| p.part == part.number())?
+            .answer
+            .as_ref()?;
+        Some(answer.parse::<Answer>().ok().as_ref() == Some(accepted))
+    }
+
+    fn stars(&self) -> usize {
+        Part::ALL
+            .iter()
+            .filter(|&&part| self.verified(part) == Some(true))
+            .count()
+    }
+}
+
+/// The example inputs of a day: the non-empty `test_input*.txt` of its data
+/// directory.
+fn examples(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut examples: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.starts_with("test_input")
+                && name.ends_with(".txt")
+                && path.metadata().is_ok_and(|m| m.len() > 0)
+        })
+        .collect();
+    examples.sort();
+    examples
+}
+
+/// Run `days` on their real inputs under `root` as `aoc run --all` does, and
+/// on their examples.
+pub fn collect(days: &[Day], root: &Path, timeout: Duration) -> Result<Vec<DayResults>> {
+    let entries = calendar::run_all(days, root, &Part::ALL, timeout);
+    entries
+        .into_iter()
+        .map(|entry| {
+            let dir = root.join(input::data_dir(entry.day));
+            let examples = match days.iter().find(|d| d.day == entry.day) {
+                Some(day) => examples(&dir)
+                    .iter()
+                    .map(|path| DayReport::run(day, path, &Part::ALL))
+                    .collect(),
+                None => Vec::new(),
+            };
+            let history = History::load(&dir)?;
+            let accepted = Part::ALL
+                .into_iter()
+                .filter_map(|part| Some((part, history.correct(part)?.parse().ok()?)))
+                .collect();
+            Ok(DayResults {
+                entry,
+                examples,
+                accepted,
+            })
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+fn class(status: Status) -> &'static str {
+    match status {
+        Status::Solved => "solved",
+        Status::Failed => "failed",
+        Status::TimedOut => "timed-out",
+        Status::Unimplemented => "unimplemented",
+    }
+}
+
+fn page_name(day: u8) -> String {
+    format!("day-{:02}.html", day)
+}
+
+/// The calendar of every day.
+pub fn index(results: &[DayResults]) -> String {
+    let stars: usize = results.iter().map(DayResults::stars).sum();
+    let mut body = format!(
+        "<h1>Advent of Code 2023</h1>\n<p>{} of {} stars verified</p>\n<div class=\"calendar\">\n",
+        stars,
+        2 * results.len()
+    );
+    for day in results {
+        let entry = &day.entry;
+        let mut cell = format!(
+            "<div class=\"number\">{}</div><div class=\"stars\">{}{}</div><div class=\"status\">{}</div>",
+            entry.day,
+            "★".repeat(day.stars()),
+            "☆".repeat(2 - day.stars()),
+            entry.status
+        );
+        if let Some(duration) = entry.duration() {
+            write!(cell, "<div class=\"time\">{:.1?}</div>", duration).unwrap();
+            cell = format!("<a href=\"{}\">{}</a>", page_name(entry.day), cell);
+        }
+        writeln!(
+            body,
+            "<div class=\"day {}\">{}</div>",
+            class(entry.status),
+            cell
+        )
+        .unwrap();
+    }
+    body.push_str("</div>\n");
+    html("Advent of Code 2023", &body)
+}
+
+/// Horizontal bars of the time spent in each step of each report.
+fn chart(reports: &[&DayReport]) -> String {
+    const ROW: usize = 20;
+    const LABEL: usize = 220;
+    const WIDTH: usize = 400;
+    let mut rows = Vec::new();
+    for report in reports {
+        let name = Path::new(&report.input.path)
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        rows.push((format!("{} parse", name), report.parse.duration_ns));
+        for part in &report.parts {
+            rows.push((format!("{} part {}", name, part.part), part.duration_ns));
+        }
+    }
+    let max = rows.iter().map(|(_, ns)| *ns).max().unwrap_or(0).max(1);
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{}\">\n",
+        LABEL + WIDTH + 100,
+        ROW * rows.len()
+    );
+    for (i, (label, ns)) in rows.iter().enumerate() {
+        let y = i * ROW;
+        let width = (*ns as f64 / max as f64 * WIDTH as f64).max(1.0);
+        writeln!(
+            svg,
+            "<text x=\"0\" y=\"{}\">{}</text>\
+             <rect x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"#090\"/>\
+             <text x=\"{:.1}\" y=\"{}\">{:.1?}</text>",
+            y + 14,
+            escape(label),
+            LABEL,
+            y + 3,
+            width,
+            ROW - 6,
+            LABEL as f64 + width + 5.0,
+            y + 14,
+            Duration::from_nanos(*ns)
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The answers of each input of a day, and the time they took.
+pub fn page(day: &DayResults) -> String {
+    let entry = &day.entry;
+    let mut body = format!(
+        "<p><a href=\"index.html\">← calendar</a></p>\n<h1>Day {}</h1>\n<p>{}</p>\n",
+        entry.day, entry.status
+    );
+    let mut reports: Vec<&DayReport> = day.examples.iter().collect();
+    reports.extend(&entry.report);
+
+    body.push_str(
+        "<h2>Answers</h2>\n<table>\n<tr><th>input</th><th>part 1</th><th>part 2</th></tr>\n",
+    );
+    for (i, report) in reports.iter().enumerate() {
+        let real = i == day.examples.len();
+        write!(body, "<tr><td>{}</td>", escape(&report.input.path)).unwrap();
+        for part in Part::ALL {
+            let cell = match (
+                &report.parse.error,
+                report.parts.iter().find(|p| p.part == part.number()),
+            ) {
+                (Some(error), _) => escape(error),
+                (None, Some(p)) => match (&p.answer, &p.error) {
+                    (Some(answer), _) => escape(answer),
+                    (None, error) => escape(error.as_deref().unwrap_or("")),
+                },
+                (None, None) => String::new(),
+            };
+            let verdict = match real.then(|| day.verified(part)).flatten() {
+                Some(true) => " <span class=\"ok\">✓ accepted</span>".to_string(),
+                Some(false) => format!(
+                    " <span class=\"wrong\">✗ accepted {}</span>",
+                    escape(&day.accepted[&part].to_string())
+                ),
+                None => String::new(),
+            };
+            write!(body, "<td><pre>{}</pre>{}</td>", cell, verdict).unwrap();
+        }
+        body.push_str("</tr>\n");
+    }
+    body.push_str("</table>\n");
+
+    if !reports.is_empty() {
+        body.push_str("<h2>Timings</h2>\n");
+        body.push_str(&chart(&reports));
+    }
+    html(&format!("Day {}", entry.day), &body)
+}
+
+/// Write the calendar and the pages of the days that were run into `dir`.
+pub fn write(dir: &Path, results: &[DayResults]) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let mut pages = vec![(dir.join("index.html"), index(results))];
+    for day in results.iter().filter(|d| d.entry.report.is_some()) {
+        pages.push((dir.join(page_name(day.entry.day)), page(day)));
+    }
+    pages
+        .into_iter()
+        .map(|(path, content)| {
+            fs::write(&path, content)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use crate::submit::{Attempt, Verdict};
+
+    #[test]
+    fn test_dashboard() {
+        let root = tempfile::tempdir().unwrap();
+        let data = root.path().join(input::data_dir(4));
+        fs::create_dir_all(&data).unwrap();
+        fs::copy("../day-04/data/test_input.txt", data.join("test_input.txt")).unwrap();
+        fs::copy("../day-04/data/test_input.txt", data.join("input.txt")).unwrap();
+        let mut history = History::load(&data).unwrap();
+        for (part, answer) in [(Part::One, "13"), (Part::Two, "31")] {
+            history
+                .record(Attempt {
+                    time: 0,
+                    part,
+                    answer: answer.to_string(),
+                    verdict: Verdict::Correct,
+                })
+                .unwrap();
+        }
+
+        let days = [registry::find(4).unwrap()];
+        let results = collect(&days, root.path(), Duration::from_secs(60)).unwrap();
+        assert_eq!(results.len(), 25);
+        let day = &results[3];
+        assert_eq!(day.examples.len(), 1);
+        assert_eq!(day.verified(Part::One), Some(true));
+        assert_eq!(day.verified(Part::Two), Some(false));
+        assert_eq!(day.stars(), 1);
+        assert_eq!(results[4].verified(Part::One), None);
+
+        let out = root.path().join("site");
+        let written = write(&out, &results).unwrap();
+        assert_eq!(written, [out.join("index.html"), out.join("day-04.html")]);
+        let index = fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(index.contains("1 of 50 stars verified"));
+        assert!(index.contains("<a href=\"day-04.html\">"));
+        let page = fs::read_to_string(out.join("day-04.html")).unwrap();
+        assert!(page.contains("✓ accepted"));
+        assert!(page.contains("✗ accepted 31"));
+        assert!(page.contains("<svg"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+    }
+}
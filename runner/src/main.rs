@@ -0,0 +1,594 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use aoc::num::{Numbers, Width};
+use aoc::{diff, input, spec, Answer, Part};
+use clap::{Parser, Subcommand, ValueEnum};
+use report::DayReport;
+
+mod anonymize;
+mod batch;
+mod calendar;
+mod client;
+mod crypt;
+mod dashboard;
+mod memory;
+mod registry;
+mod repl;
+mod report;
+mod scaffold;
+mod serve;
+mod submit;
+mod trace;
+mod watch;
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: memory::Counting = memory::Counting;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DumpFormat {
+    Json,
+    Yaml,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the puzzle input of one or more days
+    Run {
+        /// May be repeated
+        #[arg(long, required_unless_present = "all")]
+        day: Vec<u8>,
+        /// Solve every day in parallel and print a summary table
+        #[arg(long, conflicts_with_all = ["day", "input"])]
+        all: bool,
+        /// With --all, seconds after which days still running are given up
+        #[arg(long, default_value_t = 60)]
+        timeout: u64,
+        /// Only solve this part (1 or 2)
+        #[arg(long)]
+        part: Option<Part>,
+        /// Defaults to day-NN/data/input.txt, only allowed for a single day
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Solve with i64, i128 or big (arbitrary precision) integers, for the
+        /// days that support it
+        #[arg(long, conflicts_with = "all")]
+        int: Option<Width>,
+        /// Fail naming the operation that overflowed, for the days that support it
+        #[arg(long, conflicts_with = "all")]
+        checked: bool,
+        /// Read the input line by line in bounded memory, for the days that
+        /// support it
+        #[arg(long, conflicts_with_all = ["all", "int", "checked"])]
+        stream: bool,
+        /// json prints one object per day and line, see report.rs for the schema;
+        /// with --all, wrapped as {"day", "status", "report"}
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Solve a day on every input of a directory, checking NAME.expected answers
+    Batch {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        dir: PathBuf,
+        #[arg(long)]
+        part: Option<Part>,
+        /// Defaults to the number of CPUs
+        #[arg(long)]
+        jobs: Option<usize>,
+        /// json prints one object per input and line: a run report plus checks
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Solve the examples of day-NN/data/spec.txt, checking their answers
+    Examples {
+        /// Defaults to every day
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// Check that the implementations of each part agree on generated inputs
+    Diff {
+        /// Defaults to every day with alternative implementations
+        #[arg(long)]
+        day: Option<u8>,
+        #[arg(long)]
+        part: Option<Part>,
+        #[arg(long, default_value_t = 100)]
+        cases: usize,
+        /// Defaults to a seed taken from the clock
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Size given to the input generator on the last case
+        #[arg(long, default_value_t = 20)]
+        max_size: usize,
+    },
+    /// Download a day's input and example into day-NN/data, unless present
+    Fetch {
+        #[arg(long)]
+        day: u8,
+    },
+    /// Query a day's parsed input interactively
+    Repl {
+        #[arg(long)]
+        day: u8,
+        /// Defaults to day-NN/data/input.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Solve a day, printing the events traced by its solvers
+    Trace {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: Option<Part>,
+        /// Defaults to day-NN/data/input.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Only print events of this kind, may be repeated
+        #[arg(long)]
+        kind: Vec<String>,
+        /// Stop printing events after this many
+        #[arg(long)]
+        limit: Option<usize>,
+        /// json prints one object per event and line, see trace.rs
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Print a day's parsed input
+    Dump {
+        #[arg(long)]
+        day: u8,
+        /// Defaults to day-NN/data/input.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = DumpFormat::Json)]
+        format: DumpFormat,
+    },
+    /// Re-run a day on its example and real inputs whenever it changes
+    Watch {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: Option<Part>,
+    },
+    /// Submit a part's answer, unless previous attempts rule it out
+    Submit {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: Part,
+        /// Defaults to the answer computed from day-NN/data/input.txt
+        #[arg(long)]
+        answer: Option<Answer>,
+    },
+    /// Write an HTML calendar of every day's answers and timings
+    Dashboard {
+        /// Directory receiving index.html and a page per day
+        #[arg(long, default_value = "target/dashboard")]
+        out: PathBuf,
+        /// Seconds after which days still running are given up
+        #[arg(long, default_value_t = 60)]
+        timeout: u64,
+    },
+    /// Answer HTTP requests to solve inputs, see serve.rs for the API
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8023")]
+        addr: String,
+        /// Largest input accepted, in bytes
+        #[arg(long, default_value_t = 1 << 20)]
+        max_bytes: usize,
+        /// Seconds given to solve an input
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+        /// Inputs solved at once, defaults to the number of CPUs
+        #[arg(long)]
+        max_solves: Option<usize>,
+    },
+    /// Rewrite a day's input so that it can be shared, checking its answers
+    Anonymize {
+        #[arg(long)]
+        day: u8,
+        /// Defaults to day-NN/data/input.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Defaults to a seed taken from the clock
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Defaults to stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Write a new key for encrypting inputs, see aoc/src/crypt.rs
+    Keygen {
+        #[arg(long, default_value = aoc::crypt::KEY_FILE)]
+        out: PathBuf,
+        /// Replace an existing key, leaving inputs encrypted with it unreadable
+        #[arg(long)]
+        force: bool,
+    },
+    /// Encrypt the inputs of day-NN/data in place
+    Encrypt {
+        /// Defaults to every day
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// Decrypt the inputs of day-NN/data in place
+    Decrypt {
+        /// Defaults to every day
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// Generate a day's crate from the template and register it
+    New {
+        #[arg(long)]
+        day: u8,
+        /// Overwrite an existing solution (puzzle inputs are kept)
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+fn clock_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+fn parts(part: Option<Part>) -> Vec<Part> {
+    part.map_or(Part::ALL.to_vec(), |p| vec![p])
+}
+
+fn run(
+    days: Vec<u8>,
+    part: Option<Part>,
+    input: Option<PathBuf>,
+    numbers: Option<Numbers>,
+    stream: bool,
+    format: Format,
+) -> Result<bool> {
+    if input.is_some() && days.len() > 1 {
+        bail!("--input needs a single --day");
+    }
+    let days = days
+        .into_iter()
+        .map(|day| {
+            let day = registry::find(day)?;
+            if stream && !day.streams() {
+                bail!("day {} cannot stream its input", day.day);
+            }
+            match numbers {
+                Some(numbers) => day.with_numbers(numbers),
+                None => Ok(day),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut ok = true;
+    for day in days {
+        let path = input
+            .clone()
+            .unwrap_or_else(|| input::data_dir(day.day).join("input.txt"));
+        let report = match stream {
+            true => DayReport::stream(&day, &path, &parts(part)),
+            false => DayReport::run(&day, &path, &parts(part)),
+        };
+        ok &= report.is_ok();
+        match format {
+            Format::Text => print!("{}", report),
+            Format::Json => println!("{}", report.to_json()),
+        }
+    }
+    Ok(ok)
+}
+
+fn run_all(part: Option<Part>, timeout: Duration, format: Format) -> Result<bool> {
+    let entries = calendar::run_all(&registry::days(), Path::new("."), &parts(part), timeout);
+    match format {
+        Format::Text => print!("{}", calendar::table(&entries)),
+        Format::Json => {
+            for entry in &entries {
+                println!("{}", serde_json::to_string(entry)?);
+            }
+        }
+    }
+    Ok(entries.iter().all(|e| {
+        matches!(
+            e.status,
+            calendar::Status::Solved | calendar::Status::Unimplemented
+        )
+    }))
+}
+
+fn batch(
+    day: u8,
+    dir: &Path,
+    part: Option<Part>,
+    jobs: Option<usize>,
+    format: Format,
+) -> Result<bool> {
+    let day = registry::find(day)?;
+    let jobs = jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    let outcomes = batch::batch(&day, dir, &parts(part), jobs)?;
+    for outcome in &outcomes {
+        match format {
+            Format::Text => print!("{}", outcome),
+            Format::Json => println!("{}", serde_json::to_string(outcome)?),
+        }
+    }
+    let failed = outcomes.iter().filter(|o| !o.is_ok()).count();
+    if let Format::Text = format {
+        println!("{} inputs, {} failed", outcomes.len(), failed);
+    }
+    Ok(failed == 0)
+}
+
+fn examples(day: Option<u8>) -> Result<bool> {
+    let days = match day {
+        Some(day) => vec![registry::find(day)?],
+        None => registry::days(),
+    };
+    let mut outcomes = Vec::new();
+    for day in days {
+        outcomes.extend(spec::check(&day, &input::data_dir(day.day))?);
+    }
+    for outcome in &outcomes {
+        println!("{}", outcome);
+    }
+    let failed = outcomes.iter().filter(|o| !o.is_ok()).count();
+    println!("{} examples, {} failed", outcomes.len(), failed);
+    Ok(failed == 0)
+}
+
+fn dump(day: u8, input: Option<PathBuf>, format: DumpFormat) -> Result<()> {
+    let day = registry::find(day)?;
+    let path = input.unwrap_or_else(|| input::data_dir(day.day).join("input.txt"));
+    let model = day.parse(&input::read(path)?)?;
+    let Some(value) = day.dump(&model) else {
+        bail!("day {} cannot be dumped", day.day);
+    };
+    match format {
+        DumpFormat::Json => println!("{}", serde_json::to_string_pretty(&value?)?),
+        DumpFormat::Yaml => print!("{}", serde_yaml::to_string(&value?)?),
+    }
+    Ok(())
+}
+
+fn anonymize(day: u8, input: Option<PathBuf>, seed: u64, output: Option<PathBuf>) -> Result<bool> {
+    let day = registry::find(day)?;
+    let path = input.unwrap_or_else(|| input::data_dir(day.day).join("input.txt"));
+    let original = input::read(path)?;
+    let anonymized = day.anonymize(&original, seed)?;
+    let checks = anonymize::verify(&day, &original, &anonymized, &Part::ALL)?;
+    match &output {
+        Some(path) => fs::write(path, &anonymized.input)
+            .with_context(|| format!("failed to write {}", path.display()))?,
+        None => print!("{}", anonymized.input),
+    }
+    // Checks go to stderr, so that stdout is only the input.
+    eprintln!("seed {}", seed);
+    for check in &checks {
+        eprintln!("day {:02} {}", day.day, check);
+    }
+    Ok(checks.iter().all(anonymize::Check::is_ok))
+}
+
+fn check(day: Option<u8>, part: Option<Part>, config: diff::Config) -> Result<bool> {
+    let days = match day {
+        Some(day) => vec![registry::find(day)?],
+        None => registry::days(),
+    };
+    println!("seed {}", config.seed);
+
+    let mut agree = true;
+    for day in days {
+        for part in parts(part) {
+            let names = day.implementations(part);
+            if names.len() < 2 {
+                continue;
+            }
+            let Some(result) = day.check(part, &config) else {
+                continue;
+            };
+            match result? {
+                None => println!(
+                    "day {:02} part {}: {} agree on {} cases",
+                    day.day,
+                    part,
+                    names.join(", "),
+                    config.cases
+                ),
+                Some(disagreement) => {
+                    agree = false;
+                    print!("{}", disagreement);
+                }
+            }
+        }
+    }
+    Ok(agree)
+}
+
+fn main() -> Result<ExitCode> {
+    match Cli::parse().command {
+        Command::Run {
+            day,
+            all,
+            timeout,
+            part,
+            input,
+            int,
+            checked,
+            stream,
+            format,
+        } => {
+            let numbers = (int.is_some() || checked).then(|| Numbers {
+                width: int.unwrap_or_default(),
+                checked,
+            });
+            let ok = if all {
+                run_all(part, Duration::from_secs(timeout), format)?
+            } else {
+                run(day, part, input, numbers, stream, format)?
+            };
+            if !ok {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Batch {
+            day,
+            dir,
+            part,
+            jobs,
+            format,
+        } => {
+            if !batch(day, &dir, part, jobs, format)? {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Examples { day } => {
+            if !examples(day)? {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Diff {
+            day,
+            part,
+            cases,
+            seed,
+            max_size,
+        } => {
+            let seed = seed.unwrap_or_else(clock_seed);
+            let config = diff::Config {
+                cases,
+                seed,
+                max_size,
+            };
+            if !check(day, part, config)? {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Fetch { day } => {
+            let mut client = client::Client::from_env()?;
+            let written = client.fetch(day, &input::data_dir(day))?;
+            if written.is_empty() {
+                println!("day {:02} inputs are already downloaded", day);
+            }
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+        }
+        Command::Repl { day, input } => {
+            let day = registry::find(day)?;
+            let path = input.unwrap_or_else(|| input::data_dir(day.day).join("input.txt"));
+            let model = day.parse(&input::read(path)?)?;
+            repl::repl(day, model)?;
+        }
+        Command::Trace {
+            day,
+            part,
+            input,
+            kind,
+            limit,
+            format,
+        } => {
+            let day = registry::find(day)?;
+            let path = input.unwrap_or_else(|| input::data_dir(day.day).join("input.txt"));
+            let input = input::read(path)?;
+            let mut writer = trace::Writer::new(io::stdout().lock(), format, kind, limit);
+            let parts = parts(part);
+            // Answers go to stderr, so that stdout is only events.
+            for (part, answer) in parts.iter().zip(writer.run(&day, &input, &parts)?) {
+                eprintln!("day {:02} part {}: {}", day.day, part, answer);
+            }
+        }
+        Command::Dump { day, input, format } => dump(day, input, format)?,
+        Command::Watch { day, part } => watch::watch(registry::find(day)?.day, part)?,
+        Command::Submit { day, part, answer } => {
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    let day = registry::find(day)?;
+                    let path = input::data_dir(day.day).join("input.txt");
+                    day.solve(&day.parse(&input::read(path)?)?, part)?
+                }
+            };
+            let mut client = client::Client::from_env()?;
+            let answer = answer.to_string();
+            let verdict = submit::submit(&mut client, &input::data_dir(day), day, part, &answer)?;
+            println!("day {:02} part {}: {} is {}", day, part, answer, verdict);
+            if verdict != submit::Verdict::Correct {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Dashboard { out, timeout } => {
+            let timeout = Duration::from_secs(timeout);
+            let results = dashboard::collect(&registry::days(), Path::new("."), timeout)?;
+            dashboard::write(&out, &results)?;
+            println!("wrote {}", out.join("index.html").display());
+        }
+        Command::Serve {
+            addr,
+            max_bytes,
+            timeout,
+            max_solves,
+        } => {
+            let limits = serve::Limits {
+                max_bytes,
+                timeout: Duration::from_secs(timeout),
+                max_solves: max_solves
+                    .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get())),
+            };
+            serve::serve(&addr, limits)?;
+        }
+        Command::Anonymize {
+            day,
+            input,
+            seed,
+            output,
+        } => {
+            if !anonymize(day, input, seed.unwrap_or_else(clock_seed), output)? {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Keygen { out, force } => {
+            aoc::crypt::write_key(&out, force)?;
+            println!("wrote {}, keep it out of version control", out.display());
+        }
+        Command::Encrypt { day } => {
+            let files = crypt::data_files(Path::new("."), day)?;
+            for path in crypt::encrypt(&aoc::crypt::Key::load()?, &files)? {
+                println!("encrypted {}", path.display());
+            }
+        }
+        Command::Decrypt { day } => {
+            let files = crypt::data_files(Path::new("."), day)?;
+            for path in crypt::decrypt(&aoc::crypt::Key::load()?, &files)? {
+                println!("decrypted {}", path.display());
+            }
+        }
+        Command::New { day, force } => {
+            for path in scaffold::new_day(Path::new("."), day, force)? {
+                println!("wrote {}", path.display());
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
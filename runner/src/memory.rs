@@ -0,0 +1,193 @@
+//! Memory usage of solvers: allocations counted by a global allocator, and
+//! the peak resident memory of the process.
+//!
+//! Measuring is opt-in: the runner only installs [`Counting`] when built with
+//! the `memory` feature, e.g. `cargo run --release --features memory -- run
+//! --day 6`, and [`measure`] reports nothing otherwise. Both figures are
+//! process-wide, so memory used by other threads is counted too: code
+//! solving alongside other days or inputs, as `run --all`, `batch` and
+//! `serve` do, runs [`unmeasured`], which says so once on stderr.
+//!
+//! Resident memory also holds the binary, stacks and pages the allocator
+//! kept, so it is at least the heap in use. It is read from `/proc`, where
+//! Linux lets a process reset its peak, and is unknown elsewhere.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fs;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Once;
+
+use serde::Serialize;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static UNMEASURED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The system allocator, counting allocations and the bytes in use.
+#[cfg_attr(not(feature = "memory"), allow(dead_code))]
+pub struct Counting;
+
+#[cfg_attr(not(feature = "memory"), allow(dead_code))]
+impl Counting {
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn freed(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Counting::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Counting::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Counting::freed(layout.size());
+    }
+
+    /// Counted as a new allocation, since growing a vector is one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Counting::freed(layout.size());
+            Counting::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// What a piece of code allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Usage {
+    pub allocations: u64,
+    /// Total size of the allocations, freed or not.
+    pub bytes: u64,
+    /// Most resident memory of the process at once, if the system tells.
+    pub peak_bytes: Option<u64>,
+    /// Most heap in use at once, above what was in use before.
+    pub peak_heap_bytes: usize,
+}
+
+/// Run `f`, along with its heap usage if the allocator is counting.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    if !cfg!(feature = "memory") || UNMEASURED.get() {
+        return (f(), None);
+    }
+    count(f)
+}
+
+/// Run `f` on this thread without measuring, for code running alongside
+/// other solvers, whose allocations would be counted as its own.
+pub fn unmeasured<T>(f: impl FnOnce() -> T) -> T {
+    static WARNING: Once = Once::new();
+    if cfg!(feature = "memory") {
+        WARNING.call_once(|| {
+            eprintln!("memory is not measured while solving in parallel, e.g. use --jobs 1")
+        });
+    }
+    let previous = UNMEASURED.replace(true);
+    let result = f();
+    UNMEASURED.set(previous);
+    result
+}
+
+fn count<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    let base = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let resident = reset_peak_resident();
+    let result = f();
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        peak_bytes: resident.then(peak_resident).flatten(),
+        peak_heap_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(base),
+    };
+    (result, Some(usage))
+}
+
+/// Make the peak resident memory the current one, see proc(5).
+fn reset_peak_resident() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+/// The peak resident memory of the process, `VmHWM` in proc(5).
+fn peak_resident() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find_map(|l| l.strip_prefix("VmHWM:"))?;
+    let kib: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(feature = "memory", ignore)]
+    fn test_count() {
+        // Tests run without the allocator installed, so only the calls made
+        // here are counted.
+        let (_, usage) = count(|| unsafe {
+            let small = Layout::from_size_align(16, 8).unwrap();
+            let big = Layout::from_size_align(1000, 8).unwrap();
+            let a = Counting.alloc(small);
+            let b = Counting.alloc_zeroed(big);
+            Counting.dealloc(b, big);
+            let a = Counting.realloc(a, small, 100);
+            Counting.dealloc(a, Layout::from_size_align(100, 8).unwrap());
+        });
+        let usage = usage.unwrap();
+        assert_eq!(usage.allocations, 3);
+        assert_eq!(usage.bytes, 1116);
+        assert_eq!(usage.peak_heap_bytes, 1016);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_peak_resident() {
+        let (_, usage) = count(|| {
+            let pages = vec![1_u8; 64 << 20];
+            pages.iter().map(|&b| b as u64).sum::<u64>()
+        });
+        let peak = usage.unwrap().peak_bytes.unwrap();
+        assert!(peak >= 64 << 20, "{} bytes", peak);
+
+        // The peak was reset, so it no longer counts the vector.
+        assert!(reset_peak_resident());
+        assert!(peak_resident().unwrap() < peak);
+    }
+
+    #[test]
+    fn test_unmeasured() {
+        let (answer, usage) = unmeasured(|| measure(|| vec![0_u8; 64].len()));
+        assert_eq!(answer, 64);
+        assert_eq!(usage, None);
+        assert!(!UNMEASURED.get());
+    }
+}
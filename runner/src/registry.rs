@@ -0,0 +1,164 @@
+pub use days::{days, find};
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    use aoc::num::{Numbers, Width};
+    use aoc::{input, Day, Part};
+    use serde_json::Value;
+
+    use super::*;
+    use crate::report::DayReport;
+
+    /// The example of a day, or its real input if it has none. Days just
+    /// generated by `aoc new` have neither.
+    fn sample(day: u8) -> Option<PathBuf> {
+        let dir = PathBuf::from("..").join(input::data_dir(day));
+        ["test_input.txt", "test_input_1.txt", "input.txt"]
+            .into_iter()
+            .map(|file| dir.join(file))
+            .find(|path| path.metadata().is_ok_and(|m| m.len() > 0))
+    }
+
+    #[test]
+    fn test_dump() {
+        let mut dumped = 0;
+        for day in days() {
+            let Some(path) = sample(day.day) else {
+                continue;
+            };
+            let model = day.parse(&input::read(path).unwrap()).unwrap();
+            let Some(value) = day.dump(&model) else {
+                continue;
+            };
+            assert!(!value.unwrap().is_null(), "day {}", day.day);
+            dumped += 1;
+        }
+        assert!(dumped >= 10, "only {} days dumped", dumped);
+    }
+
+    #[test]
+    fn test_examples() {
+        let mut failed = Vec::new();
+        for day in days() {
+            let dir = PathBuf::from("..").join(input::data_dir(day.day));
+            let outcomes = aoc::spec::check(&day, &dir).unwrap();
+            failed.extend(
+                outcomes
+                    .iter()
+                    .filter(|o| !o.is_ok())
+                    .map(|o| o.to_string()),
+            );
+        }
+        assert!(failed.is_empty(), "{}", failed.join("\n"));
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut streamed = 0;
+        for day in days().into_iter().filter(Day::streams) {
+            let dir = PathBuf::from("..").join(input::data_dir(day.day));
+            for path in [sample(day.day).unwrap(), dir.join("input.txt")] {
+                let model = day.parse(&input::read(&path).unwrap()).unwrap();
+                for part in Part::ALL {
+                    let mut reader = input::open(&path).unwrap();
+                    let answer = day.solve_stream(&mut reader, part);
+                    match day.solve(&model, part) {
+                        Ok(expected) => assert_eq!(
+                            answer.unwrap(),
+                            expected,
+                            "{} part {}",
+                            path.display(),
+                            part
+                        ),
+                        Err(_) => assert!(answer.is_err(), "{} part {}", path.display(), part),
+                    }
+                }
+            }
+            streamed += 1;
+        }
+        assert_eq!(streamed, 5);
+        assert!(find(5)
+            .unwrap()
+            .solve_stream(&mut &b""[..], Part::One)
+            .is_err());
+    }
+
+    #[test]
+    fn test_numbers() {
+        let mut failed = Vec::new();
+        for day in days() {
+            let dir = PathBuf::from("..").join(input::data_dir(day.day));
+            for width in [Width::I64, Width::I128, Width::Big] {
+                for checked in [false, true] {
+                    let Ok(day) = day.with_numbers(Numbers { width, checked }) else {
+                        continue;
+                    };
+                    let outcomes = aoc::spec::check(&day, &dir).unwrap();
+                    failed.extend(
+                        outcomes
+                            .iter()
+                            .filter(|o| !o.is_ok())
+                            .map(|o| format!("{} ({}, checked: {})", o, width, checked)),
+                    );
+                }
+            }
+        }
+        assert!(failed.is_empty(), "{}", failed.join("\n"));
+        assert!(find(4).unwrap().with_numbers(Numbers::default()).is_ok());
+        assert!(find(8).unwrap().with_numbers(Numbers::default()).is_err());
+    }
+
+    /// Solve the samples with a runner built the other way round from this
+    /// one regarding `parallel`, expecting the same answers and errors. It
+    /// builds to its own target directory, as watch.rs does.
+    #[test]
+    fn test_parallel() {
+        let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let mut compared = 0;
+        for day in days() {
+            let Some(path) = sample(day.day) else {
+                continue;
+            };
+            let mut command = Command::new(&cargo);
+            command
+                .current_dir("..")
+                .args(["run", "--quiet", "--package", "runner"])
+                .args(["--target-dir", "target/parallel"]);
+            if !cfg!(feature = "parallel") {
+                command.args(["--features", "parallel"]);
+            }
+            let output = command
+                .args(["--", "run", "--day", &day.day.to_string()])
+                .args(["--format", "json", "--input"])
+                .arg(path.canonicalize().unwrap())
+                .output()
+                .unwrap();
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let Ok(other) = serde_json::from_str::<Value>(&stdout) else {
+                panic!("{}", String::from_utf8_lossy(&output.stderr));
+            };
+
+            let report = DayReport::run(&day, &path, &Part::ALL);
+            let answers = |parts: &Value| -> Vec<(Value, Value)> {
+                parts
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|p| (p["answer"].clone(), p["error"].clone()))
+                    .collect()
+            };
+            assert_eq!(
+                answers(&other["parts"]),
+                answers(&serde_json::to_value(&report).unwrap()["parts"]),
+                "day {}",
+                day.day
+            );
+            compared += 1;
+        }
+        assert!(compared >= 10, "only {} days compared", compared);
+    }
+}
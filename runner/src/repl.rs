@@ -0,0 +1,214 @@
+use std::env;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Result};
+use aoc::{diff, Day, Model, Part};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+const BUILTINS: [(&str, &str, &str); 3] = [
+    ("help", "", "list the commands"),
+    ("part", "<1|2>", "solve a part"),
+    ("quit", "", "leave"),
+];
+
+/// Completes the first word of a line with the commands of a day.
+struct Commands(Vec<&'static str>);
+
+impl Commands {
+    fn complete(&self, line: &str) -> Vec<&'static str> {
+        if line.contains(char::is_whitespace) {
+            return Vec::new();
+        }
+        self.0
+            .iter()
+            .copied()
+            .filter(|name| name.starts_with(line))
+            .collect()
+    }
+}
+
+impl Completer for Commands {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let candidates = Commands::complete(self, &line[..pos])
+            .into_iter()
+            .map(|name| Pair {
+                display: name.to_string(),
+                replacement: format!("{} ", name),
+            })
+            .collect();
+        Ok((0, candidates))
+    }
+}
+
+impl Hinter for Commands {
+    type Hint = String;
+}
+
+impl Highlighter for Commands {}
+
+impl Validator for Commands {}
+
+impl Helper for Commands {}
+
+fn help(day: &Day) -> String {
+    let commands = day.commands();
+    let rows: Vec<(&str, &str, &str)> = commands
+        .iter()
+        .map(|c| (c.name, c.args, c.help))
+        .chain(BUILTINS)
+        .collect();
+    let width = rows
+        .iter()
+        .map(|(name, args, _)| name.len() + args.len() + 1)
+        .max()
+        .unwrap_or(0);
+    rows.iter()
+        .map(|(name, args, help)| {
+            let usage = format!("{} {}", name, args);
+            format!("  {:width$}  {}", usage, help, width = width)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Run `what`, turning a panic into an error rather than leaving the REPL.
+fn guarded<T>(what: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    diff::catch(f).unwrap_or_else(|panic| Err(anyhow!("{} panicked: {}", what, panic)))
+}
+
+/// Answer one line of input, or `None` to leave.
+fn eval(day: &Day, model: &Model, line: &str) -> Option<Result<String>> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let (&command, args) = words.split_first()?;
+    let result = match command {
+        "quit" | "exit" => return None,
+        "help" => Ok(help(day)),
+        "part" => match args {
+            [part] => part
+                .parse::<Part>()
+                .map_err(|e| anyhow!(e))
+                .and_then(|part| guarded(&format!("part {}", part), || day.solve(model, part)))
+                .map(|answer| answer.to_string()),
+            _ => Err(anyhow!("usage: part <1|2>")),
+        },
+        _ if day.commands().iter().any(|c| c.name == command) => {
+            guarded(command, || day.query(model, command, args))
+        }
+        _ => Err(anyhow!("unknown command {:?}, try help", command)),
+    };
+    Some(result)
+}
+
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".aoc_history"))
+}
+
+/// Query the model of `day` interactively until end of input.
+pub fn repl(day: Day, model: Model) -> Result<()> {
+    let mut names: Vec<&str> = day.commands().iter().map(|c| c.name).collect();
+    names.extend(BUILTINS.map(|(name, _, _)| name));
+    let mut editor = Editor::new()?;
+    editor.set_helper(Some(Commands(names)));
+    let history = history_path();
+    if let Some(path) = &history {
+        // There is no history yet on the first run.
+        let _ = editor.load_history(path);
+    }
+
+    println!("day {:02}, type help for the commands", day.day);
+    let prompt = format!("day {:02}> ", day.day);
+    loop {
+        let line = match editor.readline(&prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(error) => bail!(error),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line.as_str())?;
+        match eval(&day, &model, &line) {
+            None => break,
+            Some(Ok(output)) => println!("{}", output),
+            Some(Err(error)) => println!("error: {:#}", error),
+        }
+    }
+    if let Some(path) = &history {
+        editor.save_history(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::input;
+
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_eval() {
+        let day = registry::find(8).unwrap();
+        let path = PathBuf::from("..").join(input::data_dir(8).join("test_input_1.txt"));
+        let model = day.parse(&input::read(path).unwrap()).unwrap();
+
+        assert_eq!(eval(&day, &model, "next AAA L").unwrap().unwrap(), "BBB");
+        assert_eq!(eval(&day, &model, "part 1").unwrap().unwrap(), "2");
+        assert!(eval(&day, &model, "help")
+            .unwrap()
+            .unwrap()
+            .contains("next <name> <L|R>"));
+        assert!(eval(&day, &model, "part 3").unwrap().is_err());
+        assert!(eval(&day, &model, "jump AAA").unwrap().is_err());
+        assert!(eval(&day, &model, "").is_none());
+        assert!(eval(&day, &model, "quit").is_none());
+    }
+
+    struct Panicking;
+
+    impl aoc::Solution for Panicking {
+        const DAY: u8 = 4;
+        type Model = ();
+
+        fn parse(_input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(_model: &()) -> Result<aoc::Answer> {
+            panic!("attempt to multiply with overflow")
+        }
+    }
+
+    #[test]
+    fn test_eval_panic() {
+        let day = Day::new::<Panicking>();
+        let model = day.parse("").unwrap();
+        let error = eval(&day, &model, "part 1").unwrap().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "part 1 panicked: attempt to multiply with overflow"
+        );
+        assert!(eval(&day, &model, "part 2").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_complete() {
+        let commands = Commands(vec!["map", "unmap", "part", "quit"]);
+        assert_eq!(commands.complete("ma"), ["map"]);
+        assert_eq!(commands.complete(""), ["map", "unmap", "part", "quit"]);
+        assert!(commands.complete("map s").is_empty());
+    }
+}
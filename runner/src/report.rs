@@ -0,0 +1,338 @@
+//! Results of solving a day, as printed by `aoc run`.
+//!
+//! With `--format json`, each day is printed as one JSON object on its own
+//! line, so that several days make a JSON Lines stream. The schema is stable:
+//! fields are only ever added, along with a bump of `version`.
+//!
+//! ```json
+//! {
+//!   "version": 2,
+//!   "day": 4,                             // 1 to 25
+//!   "input": {
+//!     "path": "day-04/data/input.txt",
+//!     "sha256": "9f86d0…",                // hex, null if unreadable or streamed
+//!     "bytes": 1234                       // null if unreadable or streamed
+//!   },
+//!   "parse": {
+//!     "duration_ns": 81234,
+//!     "memory": {                         // null unless built with `memory`,
+//!                                         // or if solved alongside others
+//!       "allocations": 120,
+//!       "bytes": 40960,                   // allocated in all
+//!       "peak_bytes": 2551808,            // most resident memory at once,
+//!                                         // null if the system does not tell
+//!       "peak_heap_bytes": 8192           // most heap in use at once
+//!     },
+//!     "error": null                       // or why reading/parsing failed
+//!   },
+//!   "parts": [                            // empty if parsing failed
+//!     {
+//!       "part": 1,
+//!       "answer": "13",                   // null on error
+//!       "kind": "int",                    // int, big, text or grid; null on error
+//!       "duration_ns": 5120,
+//!       "memory": null,                   // as for parse
+//!       "error": null                     // e.g. "not implemented"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Answers are always strings, so that big integers survive JSON parsers;
+//! grids are joined with `\n`. Version 2 added `memory`, see memory.rs.
+
+use std::fmt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use aoc::{diff, input, Answer, Day, Part, Unimplemented};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::memory::{self, Usage};
+
+pub const VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DayReport {
+    pub version: u32,
+    pub day: u8,
+    pub input: InputReport,
+    pub parse: ParseReport,
+    pub parts: Vec<PartReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InputReport {
+    pub path: String,
+    pub sha256: Option<String>,
+    pub bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ParseReport {
+    pub duration_ns: u64,
+    pub memory: Option<Usage>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub kind: Option<&'static str>,
+    pub duration_ns: u64,
+    pub memory: Option<Usage>,
+    pub error: Option<String>,
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+fn sha256(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn caught<T>(result: Result<anyhow::Result<T>, String>) -> anyhow::Result<T> {
+    result.unwrap_or_else(|panic| Err(anyhow!("panicked: {}", panic)))
+}
+
+impl PartReport {
+    fn new(
+        part: Part,
+        result: anyhow::Result<Answer>,
+        duration: Duration,
+        memory: Option<Usage>,
+    ) -> Self {
+        let (answer, kind, error) = match result {
+            Ok(answer) => (Some(answer.to_string()), Some(answer.kind()), None),
+            Err(error) => (None, None, Some(format!("{:#}", error))),
+        };
+        PartReport {
+            part: part.number(),
+            answer,
+            kind,
+            duration_ns: nanos(duration),
+            memory,
+            error,
+        }
+    }
+}
+
+impl DayReport {
+    fn new(day: &Day, path: String) -> Self {
+        DayReport {
+            version: VERSION,
+            day: day.day,
+            input: InputReport {
+                path,
+                sha256: None,
+                bytes: None,
+            },
+            parse: ParseReport {
+                duration_ns: 0,
+                memory: None,
+                error: None,
+            },
+            parts: Vec::new(),
+        }
+    }
+
+    /// Solve `parts` of `day` on the input at `path`. Failures, panics
+    /// included, are recorded in the report rather than returned.
+    pub fn run(day: &Day, path: &Path, parts: &[Part]) -> Self {
+        let name = path.display().to_string();
+        match input::read(path) {
+            Ok(input) => DayReport::solve(day, name, &input, parts),
+            Err(error) => {
+                let mut report = DayReport::new(day, name);
+                report.parse.error = Some(format!("{:#}", error));
+                report
+            }
+        }
+    }
+
+    /// Solve `parts` of `day` on `input`, reported as coming from `path`.
+    pub fn solve(day: &Day, path: String, input: &str, parts: &[Part]) -> Self {
+        let mut report = DayReport::new(day, path);
+        report.input.sha256 = Some(sha256(input.as_bytes()));
+        report.input.bytes = Some(input.len());
+
+        let start = Instant::now();
+        let (model, usage) = memory::measure(|| caught(diff::catch(|| day.parse(input))));
+        report.parse.duration_ns = nanos(start.elapsed());
+        report.parse.memory = usage;
+        let model = match model {
+            Ok(model) => model,
+            Err(error) => {
+                report.parse.error = Some(format!("{:#}", error));
+                return report;
+            }
+        };
+        for &part in parts {
+            let start = Instant::now();
+            let (result, usage) =
+                memory::measure(|| caught(diff::catch(|| day.solve(&model, part))));
+            report
+                .parts
+                .push(PartReport::new(part, result, start.elapsed(), usage));
+        }
+        report
+    }
+
+    /// Solve `parts` of `day` reading the input at `path` line by line, anew
+    /// for each part. The input is not hashed, and nothing is parsed upfront.
+    pub fn stream(day: &Day, path: &Path, parts: &[Part]) -> Self {
+        let mut report = DayReport::new(day, path.display().to_string());
+        for &part in parts {
+            let start = Instant::now();
+            let (result, usage) = memory::measure(|| {
+                caught(diff::catch(|| {
+                    let mut input = input::open(path)?;
+                    day.solve_stream(&mut input, part)
+                }))
+            });
+            report
+                .parts
+                .push(PartReport::new(part, result, start.elapsed(), usage));
+        }
+        report
+    }
+
+    /// Whether the input was parsed and every part solved, but those that
+    /// are not implemented.
+    pub fn is_ok(&self) -> bool {
+        let unimplemented = Unimplemented.to_string();
+        self.parse.error.is_none()
+            && self
+                .parts
+                .iter()
+                .all(|p| p.error.as_ref().is_none_or(|e| *e == unimplemented))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("reports serialize")
+    }
+}
+
+fn usage(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    usage: &Option<Usage>,
+    duration_ns: u64,
+) -> fmt::Result {
+    let Some(usage) = usage else {
+        return Ok(());
+    };
+    writeln!(
+        f,
+        "  {:<8} {:>9} allocations {:>12} bytes  peak {:>12} bytes resident, {:>12} heap  {:?}",
+        label,
+        usage.allocations,
+        usage.bytes,
+        usage
+            .peak_bytes
+            .map_or("?".to_string(), |bytes| bytes.to_string()),
+        usage.peak_heap_bytes,
+        Duration::from_nanos(duration_ns)
+    )
+}
+
+/// The text format: one `day NN part P: answer` line per part, followed by
+/// memory usage when it was measured.
+impl fmt::Display for DayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(error) = &self.parse.error {
+            return writeln!(f, "day {:02}: {}", self.day, error);
+        }
+        for part in &self.parts {
+            write!(f, "day {:02} part {}:", self.day, part.part)?;
+            match (&part.answer, &part.error) {
+                (Some(answer), _) if part.kind == Some("grid") => writeln!(f, "\n{}", answer)?,
+                (Some(answer), _) => writeln!(f, " {}", answer)?,
+                (None, Some(error)) => writeln!(f, " {}", error)?,
+                (None, None) => writeln!(f)?,
+            }
+        }
+        usage(f, "parse", &self.parse.memory, self.parse.duration_ns)?;
+        for part in &self.parts {
+            let label = format!("part {}", part.part);
+            usage(f, &label, &part.memory, part.duration_ns)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::registry;
+
+    fn test_input(day: u8) -> PathBuf {
+        PathBuf::from("..").join(input::data_dir(day).join("test_input.txt"))
+    }
+
+    #[test]
+    fn test_report() {
+        let day = registry::find(4).unwrap();
+        let report = DayReport::run(&day, &test_input(4), &Part::ALL);
+        assert!(report.is_ok());
+        assert_eq!(report.parts.len(), 2);
+        assert_eq!(report.parts[0].answer.as_deref(), Some("13"));
+        assert_eq!(report.parts[1].answer.as_deref(), Some("30"));
+        assert_eq!(report.input.sha256.as_ref().unwrap().len(), 64);
+        assert!(report
+            .to_string()
+            .starts_with("day 04 part 1: 13\nday 04 part 2: 30\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["version"], 2);
+        assert_eq!(json["day"], 4);
+        assert_eq!(json["parts"][1]["part"], 2);
+        assert_eq!(json["parts"][1]["answer"], "30");
+        assert_eq!(json["parts"][1]["kind"], "int");
+        assert!(json["parts"][1]["error"].is_null());
+        assert!(json["parse"]["duration_ns"].is_u64());
+        assert_eq!(json["parse"]["memory"].is_null(), !cfg!(feature = "memory"));
+        assert!(!report.to_json().contains('\n'));
+    }
+
+    #[test]
+    fn test_report_errors() {
+        let day = registry::find(1).unwrap();
+        let report = DayReport::run(&day, &test_input(1), &[Part::One]);
+        assert_eq!(report.parts[0].error.as_deref(), Some("not implemented"));
+        assert_eq!(report.parts[0].kind, None);
+        assert!(report.is_ok());
+
+        let day = registry::find(4).unwrap();
+        let mut report = DayReport::run(&day, &test_input(4), &Part::ALL);
+        report.parts[1].answer = None;
+        report.parts[1].error = Some("panicked: attempt to add with overflow".to_string());
+        assert!(!report.is_ok());
+
+        let report = DayReport::stream(&day, Path::new("missing.txt"), &Part::ALL);
+        assert!(report.parse.error.is_none());
+        assert!(!report.is_ok());
+
+        let report = DayReport::run(&day, Path::new("missing.txt"), &Part::ALL);
+        assert!(!report.is_ok());
+        assert!(report.parts.is_empty());
+        assert_eq!(report.input.sha256, None);
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            sha256(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, ensure, Context, Result};
+
+const TEMPLATES: [(&str, &str); 3] = [
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.tmpl")),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+];
+
+/// Written unless present, as they hold puzzle data.
+const DATA_FILES: [(&str, &str); 3] = [
+    ("input.txt", ""),
+    ("test_input.txt", ""),
+    ("spec.txt", include_str!("../templates/spec.txt.tmpl")),
+];
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{DAY}}", &format!("{:02}", day))
+        .replace("{{DAY_NUMBER}}", &day.to_string())
+}
+
+/// Generate the crate of `day` under `root` from the templates, and register
+/// it in the `days` crate, shared by the runner and the Python bindings.
+/// Puzzle inputs are never overwritten, and an existing solution only with
+/// `force`.
+pub fn new_day(root: &Path, day: u8, force: bool) -> Result<Vec<PathBuf>> {
+    ensure!(
+        (1..=25).contains(&day),
+        "day {} is not in the calendar",
+        day
+    );
+    let dir = root.join(format!("day-{:02}", day));
+    if dir.join("src/lib.rs").exists() && !force {
+        bail!(
+            "{} already has a solution, use --force to regenerate it",
+            dir.display()
+        );
+    }
+
+    let mut written = Vec::new();
+    for (file, template) in TEMPLATES {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, render(template, day))
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        written.push(path);
+    }
+    for (file, template) in DATA_FILES {
+        let path = dir.join("data").join(file);
+        if !path.exists() {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, render(template, day))?;
+            written.push(path);
+        }
+    }
+
+    let days = root.join("days");
+    written.extend(register(
+        &days.join("Cargo.toml"),
+        Some("[dependencies]"),
+        "day-",
+        &format!("day-{:02} = {{ path = \"../day-{:02}\" }}", day, day),
+    )?);
+    written.extend(register(
+        &days.join("src/lib.rs"),
+        None,
+        "<day_",
+        &format!("        Day::new::<day_{:02}::Day{:02}>(),", day, day),
+    )?);
+    Ok(written)
+}
+
+/// The day a registration line is about, found after `marker`.
+fn registered_day(line: &str, marker: &str) -> Option<u8> {
+    let start = line.find(marker)? + marker.len();
+    line.get(start..start + 2)?.parse().ok()
+}
+
+/// Insert `entry` among the lines registering days in `path`, keeping them
+/// sorted. Only lines of `section` count, if given, as the manifest also
+/// names days in its features. Does nothing if the day is already registered.
+fn register(
+    path: &Path,
+    section: Option<&str>,
+    marker: &str,
+    entry: &str,
+) -> Result<Option<PathBuf>> {
+    let day = registered_day(entry, marker).expect("entry names its day");
+    let content =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let mut lines: Vec<&str> = content.lines().collect();
+
+    let mut current = None;
+    let registered: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            if line.starts_with('[') {
+                current = Some(line.trim());
+            }
+            if section.is_some() && current != section {
+                return None;
+            }
+            Some((i, registered_day(line, marker)?))
+        })
+        .collect();
+    if registered.iter().any(|&(_, d)| d == day) {
+        return Ok(None);
+    }
+    let Some(&(first, _)) = registered.first() else {
+        bail!("no day is registered in {}", path.display());
+    };
+    let at = registered
+        .iter()
+        .rfind(|&&(_, d)| d < day)
+        .map_or(first, |&(i, _)| i + 1);
+    lines.insert(at, entry);
+
+    fs::write(path, lines.join("\n") + "\n")?;
+    Ok(Some(path.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days_fixture(root: &Path) {
+        fs::create_dir_all(root.join("days/src")).unwrap();
+        fs::write(
+            root.join("days/Cargo.toml"),
+            "[dependencies]\n\
+             aoc = { path = \"../aoc\" }\n\
+             day-01 = { path = \"../day-01\" }\n\
+             day-03 = { path = \"../day-03\" }\n\
+             \n\
+             [features]\n\
+             parallel = [\n    \
+             \"day-01/parallel\",\n    \
+             \"day-03/parallel\",\n\
+             ]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("days/src/lib.rs"),
+            "pub fn days() -> Vec<Day> {\n    vec![\n        \
+             Day::new::<day_01::Day01>(),\n        \
+             Day::differential::<day_03::Day03>(),\n    ]\n}\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = tempfile::tempdir().unwrap();
+        days_fixture(root.path());
+        new_day(root.path(), 2, false).unwrap();
+
+        let lib = fs::read_to_string(root.path().join("day-02/src/lib.rs")).unwrap();
+        assert!(lib.contains("impl aoc::Solution for Day02 {"));
+        assert!(lib.contains("const DAY: u8 = 2;"));
+        assert!(root.path().join("day-02/data/test_input.txt").exists());
+        let spec = aoc::spec::load(&root.path().join("day-02/data")).unwrap();
+        assert_eq!(spec.len(), 1);
+        assert!(spec[0].expected.is_empty());
+
+        let manifest = fs::read_to_string(root.path().join("days/Cargo.toml")).unwrap();
+        assert!(manifest.contains(
+            "day-01 = { path = \"../day-01\" }\n\
+             day-02 = { path = \"../day-02\" }\n\
+             day-03"
+        ));
+        assert!(manifest.contains(
+            "parallel = [\n    \
+             \"day-01/parallel\",\n    \
+             \"day-03/parallel\",\n\
+             ]"
+        ));
+        let registry = fs::read_to_string(root.path().join("days/src/lib.rs")).unwrap();
+        assert!(registry.contains(
+            "Day::new::<day_01::Day01>(),\n        \
+             Day::new::<day_02::Day02>(),\n        \
+             Day::differential::<day_03::Day03>(),"
+        ));
+    }
+
+    #[test]
+    fn test_regenerate() {
+        let root = tempfile::tempdir().unwrap();
+        days_fixture(root.path());
+        new_day(root.path(), 4, false).unwrap();
+        let input = root.path().join("day-04/data/input.txt");
+        fs::write(&input, "puzzle").unwrap();
+
+        assert!(new_day(root.path(), 4, false).is_err());
+        new_day(root.path(), 4, true).unwrap();
+        assert_eq!(fs::read_to_string(input).unwrap(), "puzzle");
+        let registry = fs::read_to_string(root.path().join("days/src/lib.rs")).unwrap();
+        assert_eq!(registry.matches("day_04").count(), 1);
+    }
+}
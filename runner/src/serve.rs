@@ -0,0 +1,348 @@
+//! A local HTTP API to the solvers, as served by `aoc serve`.
+//!
+//! - `GET /days` lists the implemented days: `{"days": [1, 2, …]}`.
+//! - `POST /days/N` with an input as body solves both parts of day `N`, or
+//!   only one with `?part=P`. The response is a run report, see report.rs;
+//!   its status is 422 if the input could not be parsed or a part failed.
+//!
+//! Other failures are answered with `{"error": "…"}`: 404 for unknown days
+//! and paths, 405 for other methods, 413 for inputs over the size limit, 503
+//! when as many inputs as allowed are being solved and 504 when solving takes
+//! longer than the time budget.
+//!
+//! Solvers cannot be interrupted, so a solve answered with 504 keeps running
+//! on its thread until it ends, and counts towards the solves allowed at once
+//! until then.
+
+use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use aoc::{Day, Part};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::memory;
+use crate::registry;
+use crate::report::DayReport;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Largest input accepted, in bytes.
+    pub max_bytes: usize,
+    /// Time given to parse and solve an input.
+    pub timeout: Duration,
+    /// Inputs solved at once, including those that ran out of time.
+    pub max_solves: usize,
+}
+
+/// The number of solves running.
+#[derive(Debug, Clone, Default)]
+pub struct Running(Arc<AtomicUsize>);
+
+/// A solve counted in [`Running`] until dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Running {
+    /// A slot for another solve, unless `max` are running.
+    fn acquire(&self, max: usize) -> Option<Slot> {
+        self.0
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()?;
+        Some(Slot(self.0.clone()))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn error(status: u16, message: impl Into<String>) -> (u16, Value) {
+    (status, json!({ "error": message.into() }))
+}
+
+fn too_large(limits: Limits) -> (u16, Value) {
+    let message = format!("inputs are limited to {} bytes", limits.max_bytes);
+    error(413, message)
+}
+
+/// The part asked for by a query string such as `part=1`.
+fn query_parts(query: &str) -> Result<Vec<Part>, String> {
+    let mut parts = Part::ALL.to_vec();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        match pair.split_once('=') {
+            Some(("part", part)) => parts = vec![part.parse()?],
+            _ => return Err(format!("unknown parameter {:?}", pair)),
+        }
+    }
+    Ok(parts)
+}
+
+/// Solve `parts` of `day` on another thread holding `slot`, giving up after
+/// `timeout`.
+fn solve(
+    day: Day,
+    input: String,
+    parts: Vec<Part>,
+    slot: Slot,
+    timeout: Duration,
+) -> Option<DayReport> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let report =
+            memory::unmeasured(|| DayReport::solve(&day, "request".to_string(), &input, &parts));
+        drop(slot);
+        // The receiver is gone if the budget ran out.
+        let _ = sender.send(report);
+    });
+    receiver.recv_timeout(timeout).ok()
+}
+
+/// The status and JSON body answering a request.
+pub fn respond(
+    days: &[Day],
+    running: &Running,
+    method: &Method,
+    url: &str,
+    body: &mut dyn Read,
+    limits: Limits,
+) -> (u16, Value) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let day = match (method, &segments[..]) {
+        (Method::Get, ["days"]) => {
+            let days: Vec<u8> = days.iter().map(|d| d.day).collect();
+            return (200, json!({ "days": days }));
+        }
+        (Method::Post, ["days", day]) => day,
+        (_, ["days"] | ["days", _]) => return error(405, format!("{} is not allowed", method)),
+        _ => return error(404, format!("no such path {}", path)),
+    };
+    let Some(&day) = day
+        .parse()
+        .ok()
+        .and_then(|n: u8| days.iter().find(|d| d.day == n))
+    else {
+        return error(404, format!("day {} is not implemented", day));
+    };
+    let parts = match query_parts(query) {
+        Ok(parts) => parts,
+        Err(message) => return error(400, message),
+    };
+
+    let mut input = Vec::new();
+    if let Err(e) = body
+        .take(limits.max_bytes as u64 + 1)
+        .read_to_end(&mut input)
+    {
+        return error(400, format!("failed to read the input: {}", e));
+    }
+    if input.len() > limits.max_bytes {
+        return too_large(limits);
+    }
+    let Ok(input) = String::from_utf8(input) else {
+        return error(400, "the input is not UTF-8");
+    };
+
+    let Some(slot) = running.acquire(limits.max_solves) else {
+        let message = format!("{} inputs are being solved, retry later", limits.max_solves);
+        return error(503, message);
+    };
+    match solve(day, input, parts, slot, limits.timeout) {
+        Some(report) => {
+            let status = if report.is_ok() { 200 } else { 422 };
+            (
+                status,
+                serde_json::to_value(&report).expect("reports serialize"),
+            )
+        }
+        None => error(
+            504,
+            format!("day {} took longer than {:?}", day.day, limits.timeout),
+        ),
+    }
+}
+
+fn handle(days: &[Day], running: &Running, mut request: Request, limits: Limits) {
+    let (status, body) = match request.body_length() {
+        Some(length) if length > limits.max_bytes => too_large(limits),
+        _ => {
+            let method = request.method().clone();
+            let url = request.url().to_string();
+            respond(days, running, &method, &url, request.as_reader(), limits)
+        }
+    };
+    let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    // The client may be gone, there is nobody to tell.
+    let _ = request.respond(response);
+}
+
+/// Serve `server` forever, a thread per request.
+pub fn run(server: Server, limits: Limits) {
+    let days = registry::days();
+    let running = Running::default();
+    for request in server.incoming_requests() {
+        let days = days.clone();
+        let running = running.clone();
+        thread::spawn(move || handle(&days, &running, request, limits));
+    }
+}
+
+/// Listen on `addr`, e.g. `127.0.0.1:8023`.
+pub fn serve(addr: &str, limits: Limits) -> Result<()> {
+    let server = Server::http(addr).map_err(|e| anyhow!("failed to listen on {}: {}", addr, e))?;
+    println!("listening on http://{}", server.server_addr());
+    run(server, limits);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: Limits = Limits {
+        max_bytes: 1000,
+        timeout: Duration::from_secs(60),
+        max_solves: 4,
+    };
+
+    fn start(limits: Limits) -> String {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", server.server_addr().to_ip().unwrap());
+        thread::spawn(move || run(server, limits));
+        base
+    }
+
+    /// The status and JSON body of a request.
+    fn call(request: ureq::Request, body: Option<&str>) -> (u16, Value) {
+        let response = match body {
+            Some(body) => request.send_string(body),
+            None => request.call(),
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(error) => panic!("{}", error),
+        };
+        let status = response.status();
+        (
+            status,
+            serde_json::from_str(&response.into_string().unwrap()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_serve() {
+        let base = start(LIMITS);
+        let example = aoc::input::read("../day-04/data/test_input.txt").unwrap();
+
+        let (status, body) = call(ureq::get(&format!("{}/days", base)), None);
+        assert_eq!(status, 200);
+        assert_eq!(body["days"][3], 4);
+
+        let (status, body) = call(ureq::post(&format!("{}/days/4", base)), Some(&example));
+        assert_eq!(status, 200);
+        assert_eq!(body["parts"][0]["answer"], "13");
+        assert_eq!(body["parts"][1]["answer"], "30");
+        assert!(body["parse"]["duration_ns"].is_u64());
+
+        let url = format!("{}/days/4?part=2", base);
+        let (status, body) = call(ureq::post(&url), Some(&example));
+        assert_eq!(status, 200);
+        assert_eq!(body["parts"].as_array().unwrap().len(), 1);
+
+        let (status, body) = call(
+            ureq::post(&format!("{}/days/4", base)),
+            Some("Card 1: x | y"),
+        );
+        assert_eq!(status, 422);
+        assert!(body["parse"]["error"].is_string());
+    }
+
+    #[test]
+    fn test_errors() {
+        let base = start(LIMITS);
+        let cases = [
+            (ureq::post(&format!("{}/days/12", base)), 404),
+            (ureq::post(&format!("{}/nowhere", base)), 404),
+            (ureq::get(&format!("{}/days/4", base)), 405),
+            (ureq::post(&format!("{}/days/4?part=3", base)), 400),
+        ];
+        for (request, expected) in cases {
+            let (status, body) = call(request, Some("input"));
+            assert_eq!(status, expected);
+            assert!(body["error"].is_string());
+        }
+
+        let big = "x".repeat(1001);
+        let (status, _) = call(ureq::post(&format!("{}/days/4", base)), Some(&big));
+        assert_eq!(status, 413);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let input = aoc::input::read("../day-10/data/input.txt").unwrap();
+        let limits = Limits {
+            max_bytes: input.len(),
+            timeout: Duration::ZERO,
+            max_solves: 1,
+        };
+        let days = registry::days();
+        let running = Running::default();
+        let mut body = input.as_bytes();
+        let (status, body) = respond(
+            &days,
+            &running,
+            &Method::Post,
+            "/days/10",
+            &mut body,
+            limits,
+        );
+        assert_eq!(status, 504);
+        assert_eq!(body["error"], "day 10 took longer than 0ns");
+    }
+
+    #[test]
+    fn test_busy() {
+        let example = aoc::input::read("../day-04/data/test_input.txt").unwrap();
+        let days = registry::days();
+        let running = Running::default();
+        let slot = running.acquire(LIMITS.max_solves).unwrap();
+        let limits = Limits {
+            max_solves: 1,
+            ..LIMITS
+        };
+        let (status, body) = respond(
+            &days,
+            &running,
+            &Method::Post,
+            "/days/4",
+            &mut example.as_bytes(),
+            limits,
+        );
+        assert_eq!(status, 503);
+        assert_eq!(body["error"], "1 inputs are being solved, retry later");
+
+        drop(slot);
+        let (status, _) = respond(
+            &days,
+            &running,
+            &Method::Post,
+            "/days/4",
+            &mut example.as_bytes(),
+            limits,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(running.0.load(Ordering::SeqCst), 0);
+    }
+}
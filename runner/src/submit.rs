@@ -0,0 +1,454 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context, Result};
+use aoc::Part;
+
+use crate::client::Client;
+
+/// How the website judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after a wrong answer, nothing was judged.
+    Wait(u64),
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Parse the article of the page answering a submission.
+    pub fn from_response(page: &str) -> Result<Self> {
+        let verdict = if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if page.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::Wait(wait_seconds(page).unwrap_or(60))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            bail!("unexpected answer page:\n{}", page)
+        };
+        Ok(verdict)
+    }
+
+    /// Whether the answer was judged, so that it must not be submitted again.
+    pub fn is_judgement(&self) -> bool {
+        !matches!(self, Verdict::Wait(_) | Verdict::WrongLevel)
+    }
+}
+
+/// The duration in "You have 1m 4s left to wait".
+fn wait_seconds(page: &str) -> Option<u64> {
+    let end = page.find(" left to wait")?;
+    let start = page[..end].rfind("You have ")? + "You have ".len();
+    page[start..end]
+        .split_whitespace()
+        .try_fold(0, |total, unit| {
+            let (n, unit) = unit.split_at(unit.find(|c: char| !c.is_ascii_digit())?);
+            let n: u64 = n.parse().ok()?;
+            match unit {
+                "h" => Some(total + n * 3600),
+                "m" => Some(total + n * 60),
+                "s" => Some(total + n),
+                _ => None,
+            }
+        })
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(seconds) => write!(f, "wait-{}", seconds),
+            Verdict::WrongLevel => write!(f, "wrong-level"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "wrong-level" => Ok(Verdict::WrongLevel),
+            _ => s
+                .strip_prefix("wait-")
+                .and_then(|seconds| seconds.parse().ok())
+                .map(Verdict::Wait)
+                .ok_or_else(|| anyhow!("invalid verdict {:?}", s)),
+        }
+    }
+}
+
+/// An answer submitted for a part, and when.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub time: u64,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Escape the backslashes, tabs and line breaks of an answer, so that it
+/// fits in the last field of a line.
+fn escape(answer: &str) -> String {
+    let mut escaped = String::with_capacity(answer.len());
+    for c in answer.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(field: &str) -> Result<String> {
+    let mut answer = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            answer.push(c);
+            continue;
+        }
+        answer.push(match chars.next() {
+            Some('\\') => '\\',
+            Some('t') => '\t',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            _ => bail!("invalid escape in {:?}", field),
+        });
+    }
+    Ok(answer)
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.time,
+            self.part,
+            self.verdict,
+            escape(&self.answer)
+        )
+    }
+}
+
+impl FromStr for Attempt {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let fields: Vec<&str> = line.splitn(4, '\t').collect();
+        let [time, part, verdict, answer] = fields[..] else {
+            bail!("invalid attempt {:?}", line);
+        };
+        Ok(Attempt {
+            time: time.parse()?,
+            part: part.parse().map_err(|e: String| anyhow!(e))?,
+            answer: unescape(answer)?,
+            verdict: verdict.parse()?,
+        })
+    }
+}
+
+/// Every answer submitted for a day, stored as tab separated lines in
+/// `data/attempts.tsv`, answers escaped with backslashes.
+pub struct History {
+    path: PathBuf,
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join("attempts.tsv")
+    }
+
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = History::path(dir);
+        let attempts = match fs::read_to_string(&path) {
+            Ok(content) => content
+                .lines()
+                .map(|line| line.parse())
+                .collect::<Result<_>>()
+                .with_context(|| format!("failed to parse {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e).context(format!("failed to read {}", path.display())),
+        };
+        Ok(History { path, attempts })
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("failed to open {}", self.path.display()))?;
+        writeln!(file, "{}", attempt)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// The accepted answer of `part`, if any.
+    pub fn correct(&self, part: Part) -> Option<&str> {
+        self.attempts
+            .iter()
+            .find(|a| a.part == part && a.verdict == Verdict::Correct)
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Refuse `answer` if submitting it is pointless: the part is solved, the
+    /// answer was already judged, it is outside the bounds given by previous
+    /// verdicts, or the website asked us to wait.
+    pub fn check(&self, part: Part, answer: &str, now: u64) -> Result<()> {
+        if answer.trim().is_empty() {
+            bail!("refusing to submit an empty answer");
+        }
+        if let Some(correct) = self.correct(part) {
+            bail!("part {} is already solved, the answer is {}", part, correct);
+        }
+        let attempts: Vec<&Attempt> = self.attempts.iter().filter(|a| a.part == part).collect();
+        if let Some(wait) = self.attempts.iter().find_map(|a| match a.verdict {
+            Verdict::Wait(seconds) if a.time + seconds > now => Some(a.time + seconds - now),
+            _ => None,
+        }) {
+            bail!("must wait {} more seconds before submitting", wait);
+        }
+        if let Some(attempt) = attempts
+            .iter()
+            .find(|a| a.answer == answer && a.verdict.is_judgement())
+        {
+            bail!(
+                "{} was already submitted, it is {}",
+                answer,
+                attempt.verdict
+            );
+        }
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |verdict| {
+                attempts
+                    .iter()
+                    .filter(move |a| a.verdict == verdict)
+                    .filter_map(|a| a.answer.parse::<i128>().ok())
+            };
+            if let Some(high) = bound(Verdict::TooHigh).min().filter(|&h| value >= h) {
+                bail!("{} is too high, {} already was", answer, high);
+            }
+            if let Some(low) = bound(Verdict::TooLow).max().filter(|&l| value <= l) {
+                bail!("{} is too low, {} already was", answer, low);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Submit `answer` for `part` unless the history of the day in `dir` already
+/// rules it out, and record the verdict.
+pub fn submit(
+    client: &mut Client,
+    dir: &Path,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<Verdict> {
+    let mut history = History::load(dir)?;
+    let time = now();
+    history.check(part, answer, time)?;
+    let verdict = Verdict::from_response(&client.answer(day, part, answer)?)?;
+    history.record(Attempt {
+        time,
+        part,
+        answer: answer.to_string(),
+        verdict,
+    })?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::MockServer;
+
+    fn attempt(part: Part, answer: &str, verdict: Verdict) -> Attempt {
+        Attempt {
+            time: 1000,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn test_verdict_from_response() {
+        let cases = [
+            (
+                "That's the right answer!  You are one gold star closer.",
+                Verdict::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Verdict::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::TooLow,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, ...",
+                Verdict::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an \
+                 answer before trying again.  You have 1m 4s left to wait.",
+                Verdict::Wait(64),
+            ),
+            (
+                "You have 34s left to wait. You gave an answer too recently",
+                Verdict::Wait(34),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Verdict::WrongLevel,
+            ),
+        ];
+        for (page, verdict) in cases {
+            assert_eq!(Verdict::from_response(page).unwrap(), verdict, "{}", page);
+            assert_eq!(verdict.to_string().parse::<Verdict>().unwrap(), verdict);
+        }
+        assert!(Verdict::from_response("<html>maintenance</html>").is_err());
+    }
+
+    #[test]
+    fn test_attempt_roundtrip() {
+        let attempt = attempt(Part::Two, "a b", Verdict::Wait(30));
+        assert_eq!(attempt.to_string(), "1000\t2\twait-30\ta b");
+        assert_eq!(attempt.to_string().parse::<Attempt>().unwrap(), attempt);
+        assert!("1000\t3\twrong\t1".parse::<Attempt>().is_err());
+
+        let attempt = Attempt {
+            answer: "#..\n\t#\\n".to_string(),
+            ..attempt
+        };
+        assert_eq!(attempt.to_string(), "1000\t2\twait-30\t#..\\n\\t#\\\\n");
+        assert_eq!(attempt.to_string().lines().count(), 1);
+        assert_eq!(attempt.to_string().parse::<Attempt>().unwrap(), attempt);
+        assert!("1000\t2\twrong\ta\\x".parse::<Attempt>().is_err());
+    }
+
+    #[test]
+    fn test_check_multiline() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = History::load(dir.path()).unwrap();
+        history
+            .record(attempt(Part::One, "#.#\n.#.\tx", Verdict::Wrong))
+            .unwrap();
+        history
+            .record(attempt(Part::One, "12", Verdict::TooHigh))
+            .unwrap();
+
+        let history = History::load(dir.path()).unwrap();
+        assert_eq!(history.attempts.len(), 2);
+        assert!(history.check(Part::One, "#.#\n.#.\tx", 2000).is_err());
+        assert!(history.check(Part::One, "#.#\n.#.", 2000).is_ok());
+        assert!(history.check(Part::One, "13", 2000).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = History::load(dir.path()).unwrap();
+        history
+            .record(attempt(Part::One, "100", Verdict::TooHigh))
+            .unwrap();
+        history
+            .record(attempt(Part::One, "20", Verdict::TooLow))
+            .unwrap();
+        history
+            .record(attempt(Part::One, "50", Verdict::Wrong))
+            .unwrap();
+        history
+            .record(attempt(Part::Two, "7", Verdict::Correct))
+            .unwrap();
+
+        let history = History::load(dir.path()).unwrap();
+        assert_eq!(history.attempts.len(), 4);
+        assert!(history.check(Part::One, "60", 2000).is_ok());
+        assert!(history.check(Part::One, "abc", 2000).is_ok());
+        assert!(history.check(Part::One, "", 2000).is_err());
+        assert!(history.check(Part::One, "50", 2000).is_err());
+        assert!(history.check(Part::One, "100", 2000).is_err());
+        assert!(history.check(Part::One, "150", 2000).is_err());
+        assert!(history.check(Part::One, "20", 2000).is_err());
+        assert!(history.check(Part::One, "-3", 2000).is_err());
+        assert!(history.check(Part::Two, "8", 2000).is_err());
+        assert_eq!(history.correct(Part::Two), Some("7"));
+    }
+
+    #[test]
+    fn test_check_wait() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = History::load(dir.path()).unwrap();
+        history
+            .record(attempt(Part::One, "5", Verdict::Wait(60)))
+            .unwrap();
+        assert!(history.check(Part::One, "5", 1030).is_err());
+        assert!(history.check(Part::One, "5", 1060).is_ok());
+        assert!(history.check(Part::Two, "5", 1030).is_err());
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = MockServer::start(|request| {
+            let page = match request.body.as_str() {
+                "level=1&answer=42" => "That's the right answer!",
+                _ => "That's not the right answer; your answer is too high.",
+            };
+            (200, format!("<article><p>{}</p></article>", page))
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let mut client = server.client();
+
+        let verdict = submit(&mut client, dir.path(), 3, Part::One, "1000").unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+        assert!(submit(&mut client, dir.path(), 3, Part::One, "2000").is_err());
+        let verdict = submit(&mut client, dir.path(), 3, Part::One, "42").unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+        assert!(submit(&mut client, dir.path(), 3, Part::One, "41").is_err());
+
+        let received = server.received();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[1].method, "POST");
+        assert_eq!(received[1].url, "/2023/day/3/answer");
+        let history = History::load(dir.path()).unwrap();
+        assert_eq!(history.correct(Part::One), Some("42"));
+        assert_eq!(history.attempts.len(), 2);
+    }
+}
@@ -0,0 +1,160 @@
+//! Trace events of solvers, as printed by `aoc trace`.
+//!
+//! With `--format json`, each event is one JSON object on its own line:
+//!
+//! ```json
+//! {"day": 4, "phase": "part 2", "kind": "copies",
+//!  "message": "card 3 adds 4 copies to cards 4–5",
+//!  "fields": {"card": 3, "copies": 4, "from": 4, "to": 5}}
+//! ```
+
+use std::io::{self, Write};
+
+use anyhow::Result;
+use aoc::trace::{self, Event, Sink, Value};
+use aoc::{Day, Part};
+use serde_json::json;
+
+use crate::Format;
+
+/// Writes the events of a day that pass the filters, one per line.
+pub struct Writer<W: Write> {
+    out: W,
+    format: Format,
+    /// Only these kinds of events are written, unless empty.
+    kinds: Vec<String>,
+    /// How many more events may be written.
+    left: usize,
+    day: u8,
+    phase: String,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(out: W, format: Format, kinds: Vec<String>, limit: Option<usize>) -> Self {
+        Writer {
+            out,
+            format,
+            kinds,
+            left: limit.unwrap_or(usize::MAX),
+            day: 0,
+            phase: String::new(),
+            error: None,
+        }
+    }
+
+    fn line(&self, event: &Event) -> String {
+        match self.format {
+            Format::Text => format!("day {:02} {} {}", self.day, self.phase, event),
+            Format::Json => {
+                let fields: serde_json::Map<String, serde_json::Value> = event
+                    .fields
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = match value {
+                            Value::Int(n) => json!(n),
+                            Value::Text(text) => json!(text),
+                        };
+                        (name.to_string(), value)
+                    })
+                    .collect();
+                json!({
+                    "day": self.day,
+                    "phase": self.phase,
+                    "kind": event.kind,
+                    "message": event.message,
+                    "fields": fields,
+                })
+                .to_string()
+            }
+        }
+    }
+
+    /// Solve `parts` of `day` on `input`, writing the events of parsing and of
+    /// each part. Returns the answers, or why there is none.
+    pub fn run(&mut self, day: &Day, input: &str, parts: &[Part]) -> Result<Vec<String>> {
+        self.day = day.day;
+        self.phase = "parse".to_string();
+        let model = trace::traced(self, || day.parse(input));
+        self.flush()?;
+        let model = model?;
+        let mut answers = Vec::new();
+        for &part in parts {
+            self.phase = format!("part {}", part);
+            let answer = trace::traced(self, || day.solve(&model, part));
+            self.flush()?;
+            answers.push(match answer {
+                Ok(answer) => answer.to_string(),
+                Err(error) => format!("{:#}", error),
+            });
+        }
+        Ok(answers)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if let Some(error) = self.error.take() {
+            return Err(error.into());
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+impl<W: Write> Sink for Writer<W> {
+    fn event(&mut self, event: &Event) {
+        let wanted = self.kinds.is_empty() || self.kinds.iter().any(|k| k == event.kind);
+        if !wanted || self.left == 0 || self.error.is_some() {
+            return;
+        }
+        self.left -= 1;
+        let line = self.line(event);
+        if let Err(error) = writeln!(self.out, "{}", line) {
+            self.error = Some(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    fn example() -> String {
+        aoc::input::read("../day-04/data/test_input.txt").unwrap()
+    }
+
+    #[test]
+    fn test_text() {
+        let day = registry::find(4).unwrap();
+        let mut out = Vec::new();
+        let mut writer = Writer::new(&mut out, Format::Text, Vec::new(), Some(2));
+        let answers = writer.run(&day, &example(), &Part::ALL).unwrap();
+        assert_eq!(answers, ["13", "30"]);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day 04 part 2 copies: card 1 adds 1 copies to cards 2–5\n\
+             day 04 part 2 copies: card 2 adds 2 copies to cards 3–4\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let day = registry::find(4).unwrap();
+        let mut out = Vec::new();
+        let kinds = vec!["copies".to_string()];
+        let mut writer = Writer::new(&mut out, Format::Json, kinds, None);
+        writer.run(&day, &example(), &[Part::Two]).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().count(), 4);
+        let event: serde_json::Value = serde_json::from_str(out.lines().nth(2).unwrap()).unwrap();
+        assert_eq!(event["phase"], "part 2");
+        assert_eq!(event["fields"]["card"], 3);
+        assert_eq!(event["fields"]["copies"], 4);
+
+        let mut out = Vec::new();
+        let kinds = vec!["step".to_string()];
+        let mut writer = Writer::new(&mut out, Format::Json, kinds, None);
+        writer.run(&day, &example(), &[Part::Two]).unwrap();
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use aoc::{input, Part};
+use serde_json::Value;
+
+const INPUTS: [&str; 2] = ["test_input.txt", "input.txt"];
+
+/// Modification times of some files, and of every file under some directories.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    pub fn take(paths: &[PathBuf]) -> Result<Self> {
+        let mut snapshot = Snapshot::default();
+        for path in paths {
+            snapshot.add(path)?;
+        }
+        Ok(snapshot)
+    }
+
+    fn add(&mut self, path: &Path) -> Result<()> {
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e).context(format!("failed to read {}", path.display())),
+        };
+        if !metadata.is_dir() {
+            self.0.insert(path.to_path_buf(), metadata.modified()?);
+            return Ok(());
+        }
+        for entry in fs::read_dir(path)? {
+            self.add(&entry?.path())?;
+        }
+        Ok(())
+    }
+}
+
+/// Answers of a run, by input file and part.
+pub type Answers = BTreeMap<(String, Part), String>;
+
+/// Parse the reports printed by `aoc run --format json`, see report.rs. A
+/// part that failed is given its error instead of an answer, and each of
+/// `parts` is given the error of an input that could not be parsed.
+fn parse_answers(input: &str, output: &str, parts: &[Part]) -> Result<Answers> {
+    let lines: Vec<&str> = output.lines().filter(|l| !l.trim().is_empty()).collect();
+    ensure!(!lines.is_empty(), "no report");
+    let mut answers = Answers::new();
+    for line in lines {
+        let report: Value = serde_json::from_str(line).context("invalid report")?;
+        if let Some(error) = report["parse"]["error"].as_str() {
+            for &part in parts {
+                answers.insert((input.to_string(), part), error.to_string());
+            }
+            continue;
+        }
+        let parts = report["parts"].as_array().context("report without parts")?;
+        for part in parts {
+            let number = part["part"].to_string();
+            let part_answer = part["answer"].as_str().or(part["error"].as_str());
+            let answer = part_answer.with_context(|| format!("part {} without answer", number))?;
+            let part = number.parse().map_err(|e: String| anyhow!(e))?;
+            answers.insert((input.to_string(), part), answer.to_string());
+        }
+    }
+    Ok(answers)
+}
+
+/// The answers of a run of the runner on `input`, whether or not every part
+/// was solved, or `None` if it printed no report, e.g. when it failed to build.
+fn read_output(input: &str, output: &Output, parts: &[Part]) -> Option<Answers> {
+    match parse_answers(input, &String::from_utf8_lossy(&output.stdout), parts) {
+        Ok(answers) => Some(answers),
+        Err(_) => {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            None
+        }
+    }
+}
+
+/// How an answer differs from the previous run.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change<'a> {
+    New(&'a str),
+    Same(&'a str),
+    Changed(&'a str, &'a str),
+}
+
+impl fmt::Display for Change<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::New(answer) => write!(f, "{}", answer),
+            Change::Same(answer) => write!(f, "{} (unchanged)", answer),
+            Change::Changed(previous, answer) => write!(f, "{} (was {})", answer, previous),
+        }
+    }
+}
+
+pub fn diff<'a>(
+    previous: &'a Answers,
+    current: &'a Answers,
+) -> Vec<(&'a (String, Part), Change<'a>)> {
+    current
+        .iter()
+        .map(|(key, answer)| {
+            let change = match previous.get(key) {
+                None => Change::New(answer),
+                Some(previous) if previous == answer => Change::Same(answer),
+                Some(previous) => Change::Changed(previous, answer),
+            };
+            (key, change)
+        })
+        .collect()
+}
+
+/// Build the runner and solve `day` on its example and real inputs. Builds go
+/// to their own target directory so that the running binary is not replaced.
+fn run(day: u8, part: Option<Part>) -> Result<Option<Answers>> {
+    let parts = part.map_or(Part::ALL.to_vec(), |part| vec![part]);
+    let mut answers = Answers::new();
+    for file in INPUTS {
+        let path = input::data_dir(day).join(file);
+        if fs::metadata(&path).map_or(true, |m| m.len() == 0) {
+            continue;
+        }
+        let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let mut command = Command::new(cargo);
+        command
+            .args(["run", "--quiet", "--package", "runner"])
+            .args(["--target-dir", "target/watch", "--", "run"])
+            .args(["--day", &day.to_string(), "--format", "json"])
+            .arg("--input")
+            .arg(&path);
+        if let Some(part) = part {
+            command.args(["--part", &part.to_string()]);
+        }
+        let output = command.output().context("failed to run cargo")?;
+        match read_output(file, &output, &parts) {
+            Some(file_answers) => answers.extend(file_answers),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(answers))
+}
+
+/// Re-run `day` whenever its sources, its inputs or the shared crate change,
+/// printing how the answers, or errors, differ from the previous run that
+/// printed reports.
+pub fn watch(day: u8, part: Option<Part>) -> Result<()> {
+    let crate_dir = PathBuf::from(format!("day-{:02}", day));
+    let paths = [
+        crate_dir.join("src"),
+        crate_dir.join("Cargo.toml"),
+        input::data_dir(day),
+        PathBuf::from("aoc/src"),
+    ];
+    let mut previous = Answers::new();
+    let mut seen = None;
+    loop {
+        let snapshot = Snapshot::take(&paths)?;
+        if seen.as_ref() != Some(&snapshot) {
+            seen = Some(snapshot);
+            println!("[day {:02}] running", day);
+            if let Some(answers) = run(day, part)? {
+                for ((file, part), change) in diff(&previous, &answers) {
+                    println!("{} part {}: {}", file, part, change);
+                }
+                previous = answers;
+            }
+            // Files touched while running are picked up by the next snapshot.
+            continue;
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use crate::report::DayReport;
+
+    #[test]
+    fn test_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let dirs = [
+            dir.path().join("src"),
+            dir.path().join("Cargo.toml"),
+            dir.path().join("missing"),
+        ];
+        fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+        let before = Snapshot::take(&dirs).unwrap();
+        assert_eq!(before.0.len(), 2);
+        assert_eq!(Snapshot::take(&dirs).unwrap(), before);
+
+        let file = fs::File::options()
+            .write(true)
+            .open(dir.path().join("src/lib.rs"))
+            .unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH).unwrap();
+        assert_ne!(Snapshot::take(&dirs).unwrap(), before);
+    }
+
+    /// The line printed for day 4 with the given answers or errors.
+    fn report(parts: &[Result<&str, &str>]) -> String {
+        let parts: Vec<Value> = parts
+            .iter()
+            .zip(1..)
+            .map(|(result, part)| {
+                let (answer, error) = match result {
+                    Ok(answer) => (Some(answer), None),
+                    Err(error) => (None, Some(error)),
+                };
+                serde_json::json!({"part": part, "answer": answer, "error": error})
+            })
+            .collect();
+        serde_json::json!({"version": 2, "day": 4, "parts": parts}).to_string() + "\n"
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let day = registry::find(4).unwrap();
+        let output =
+            DayReport::run(&day, Path::new("../day-04/data/test_input.txt"), &Part::ALL).to_json();
+        let answers = parse_answers("test_input.txt", &output, &Part::ALL).unwrap();
+        assert_eq!(answers[&("test_input.txt".to_string(), Part::One)], "13");
+        assert_eq!(answers[&("test_input.txt".to_string(), Part::Two)], "30");
+
+        let grid = "#..\n.#.\n..#";
+        let answers = parse_answers("input.txt", &report(&[Ok(grid)]), &Part::ALL).unwrap();
+        assert_eq!(answers[&("input.txt".to_string(), Part::One)], grid);
+        assert!(parse_answers("input.txt", "day 04 part 1: 13\n", &Part::ALL).is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let output = report(&[Ok("13"), Err("not implemented")]);
+        let previous = parse_answers("input.txt", &output, &Part::ALL).unwrap();
+        assert_eq!(previous[&("input.txt".to_string(), Part::One)], "13");
+
+        let mut current =
+            parse_answers("input.txt", &report(&[Ok("13"), Ok("30")]), &Part::ALL).unwrap();
+        current.extend(parse_answers("test_input.txt", &report(&[Ok("1")]), &Part::ALL).unwrap());
+        let changes: Vec<String> = diff(&previous, &current)
+            .into_iter()
+            .map(|((file, part), change)| format!("{} {}: {}", file, part, change))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "input.txt 1: 13 (unchanged)",
+                "input.txt 2: 30 (was not implemented)",
+                "test_input.txt 1: 1",
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_output() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let failed = |stdout: &str, stderr: &str| Output {
+            status: ExitStatusExt::from_raw(1 << 8),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        };
+        let output = failed(
+            &report(&[Ok("13"), Err("attempt to add with overflow")]),
+            "",
+        );
+        let answers = read_output("input.txt", &output, &Part::ALL).unwrap();
+        assert_eq!(answers[&("input.txt".to_string(), Part::One)], "13");
+        assert_eq!(
+            answers[&("input.txt".to_string(), Part::Two)],
+            "attempt to add with overflow"
+        );
+
+        let day = registry::find(4).unwrap();
+        let unparsable = DayReport::solve(&day, "input.txt".to_string(), "Card 1: 1 | x", &[]);
+        let answers = read_output(
+            "input.txt",
+            &failed(&unparsable.to_json(), ""),
+            &[Part::Two],
+        );
+        let answers = answers.unwrap();
+        assert_eq!(answers.len(), 1);
+        assert!(answers[&("input.txt".to_string(), Part::Two)].starts_with("invalid number"));
+
+        assert_eq!(
+            read_output("input.txt", &failed("", "error[E0308]"), &Part::ALL),
+            None
+        );
+    }
+}